@@ -0,0 +1,137 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields};
+
+use crate::util::{
+    add_target_param, is_phantom_data, mentions, replace_ident, strip_param_attrs, target_param,
+    unsized_param,
+};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let param = unsized_param(&input.generics, "coerce_unsized")?;
+    let target = target_param(&param);
+
+    let mut generics = strip_param_attrs(&input.generics);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let source_ty = quote!(#name #ty_generics);
+    let target_ty = replace_ident(&source_ty, &param, &target);
+    add_target_param(&mut generics, &param, &target)?;
+
+    let mut bounds = Vec::new();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, construct) =
+                coerce_fields(quote!(#name), &data.fields, &param, &target, &mut bounds)?;
+            if bounds.is_empty() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    format!("no field mentions the type parameter `{param}`"),
+                ));
+            }
+            quote!(let #pattern = self; #construct)
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_name = &variant.ident;
+                    let (pattern, construct) = coerce_fields(
+                        quote!(#name::#variant_name),
+                        &variant.fields,
+                        &param,
+                        &target,
+                        &mut bounds,
+                    )?;
+                    Ok(quote!(#pattern => #construct,))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            if bounds.is_empty() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    format!("no variant has a field mentioning the type parameter `{param}`"),
+                ));
+            }
+            quote!(match self { #(#arms)* })
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`CoerceUnsized` cannot be derived for unions",
+            ))
+        }
+    };
+
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.extend(
+        bounds
+            .into_iter()
+            .map(|(source, target)| -> syn::WherePredicate {
+                syn::parse_quote!(#source: ::unsizing_experiments::coerce_unsized::CoerceUnsized<#target>)
+            }),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::unsizing_experiments::coerce_unsized::CoerceUnsized<#target_ty>
+            for #source_ty #where_clause
+        {
+            fn coerce_unsized(self) -> #target_ty {
+                #body
+            }
+        }
+    })
+}
+
+/// Produces a pattern destructuring the fields and an expression reconstructing them with the
+/// field mentioning `param` coerced. The type of that field and its target are pushed to `bounds`.
+fn coerce_fields(
+    path: TokenStream,
+    fields: &Fields,
+    param: &Ident,
+    target: &Ident,
+    bounds: &mut Vec<(TokenStream, TokenStream)>,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let mut coerced = None;
+    let mut patterns = Vec::new();
+    let mut constructs = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let binding = format_ident!("__field{}", i);
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(i);
+                quote!(#index)
+            }
+        };
+        let value = if !mentions(&field.ty, param) {
+            patterns.push(quote!(#member: #binding));
+            quote!(#binding)
+        } else if is_phantom_data(&field.ty) {
+            patterns.push(quote!(#member: _));
+            quote!(::core::marker::PhantomData)
+        } else {
+            patterns.push(quote!(#member: #binding));
+            if let Some(first) = coerced.replace(field) {
+                let mut error = syn::Error::new_spanned(
+                    &field.ty,
+                    format!("only a single field may mention the type parameter `{param}`"),
+                );
+                error.combine(syn::Error::new_spanned(
+                    &first.ty,
+                    format!("`{param}` is also mentioned here"),
+                ));
+                return Err(error);
+            }
+            let ty = &field.ty;
+            bounds.push((quote!(#ty), replace_ident(ty, param, target)));
+            quote!(::unsizing_experiments::coerce_unsized::CoerceUnsized::coerce_unsized(#binding))
+        };
+        constructs.push(quote!(#member: #value));
+    }
+    Ok((
+        quote!(#path { #(#patterns),* }),
+        quote!(#path { #(#constructs),* }),
+    ))
+}
@@ -0,0 +1,43 @@
+//! Derive macros for the traits of `unsizing-experiments`, emulating the impls the compiler would
+//! otherwise have to check or generate.
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod coerce_unsized;
+mod unsize;
+mod util;
+
+/// Derives a delegating `CoerceUnsized<Foo<U>>` impl for `Foo<T>`.
+///
+/// The coerced type parameter is either the only type parameter of the type, or the one marked
+/// with `#[coerce_unsized]`. Per struct or enum variant, only a single field may mention the
+/// parameter, which will be coerced via its own `CoerceUnsized` impl. `PhantomData` fields are
+/// allowed to mention the parameter as well, they are recreated for the target type.
+#[proc_macro_derive(CoerceUnsized, attributes(coerce_unsized))]
+pub fn derive_coerce_unsized(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    coerce_unsized::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives the `FromMetadataUnsize<Foo<U>>` impl for `Foo<T>` that the compiler provides for
+/// structs whose last field involves `T`.
+///
+/// The unsized type parameter is either the only type parameter of the struct, or the one marked
+/// with `#[unsize]`. Only the last field of the struct may mention that parameter.
+///
+/// Alternatively, for structs storing their elements inline in an array with a separate length
+/// field, `#[unsize(unsafe(target = [T], data = xs, len = len))]` derives `Unsize<[T]>` and
+/// `UnsizeMut<[T]>` impls that project to the `xs` field and read the metadata from the `len`
+/// field. The `len` field must be a `usize`, and reading it panics if it exceeds the capacity of
+/// the array. `target = str` is accepted as well. The elements of the array have to be layout
+/// compatible with the target's elements, and it is up to the user to uphold that the first `len`
+/// of them are valid for the target, hence the `unsafe`.
+#[proc_macro_derive(Unsize, attributes(unsize))]
+pub fn derive_unsize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    unsize::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,223 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Attribute, Data, DataStruct, DeriveInput, Ident, Member, Token, Type};
+
+use crate::util::{
+    add_target_param, mentions, replace_ident, strip_param_attrs, target_param, unsized_param,
+};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`Unsize` can only be derived for structs",
+        ));
+    };
+    if let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("unsize"))
+    {
+        return expand_projection(&input, data, attr);
+    }
+    let param = unsized_param(&input.generics, "unsize")?;
+    let target = target_param(&param);
+
+    // emulate the compiler's rules for struct unsizing, only the last field may involve `param`
+    let Some(last) = data.fields.iter().next_back() else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`Unsize` can't be derived for structs without fields",
+        ));
+    };
+    if let Some(field) = data
+        .fields
+        .iter()
+        .rev()
+        .skip(1)
+        .find(|field| mentions(&field.ty, &param))
+    {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            format!("only the last field may mention the type parameter `{param}`"),
+        ));
+    }
+    if !mentions(&last.ty, &param) {
+        return Err(syn::Error::new_spanned(
+            &last.ty,
+            format!("the last field has to mention the type parameter `{param}`"),
+        ));
+    }
+
+    let mut generics = strip_param_attrs(&input.generics);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let source_ty = quote!(#name #ty_generics);
+    let target_ty = replace_ident(&source_ty, &param, &target);
+    add_target_param(&mut generics, &param, &target)?;
+
+    let field_ty = &last.ty;
+    let target_field_ty = replace_ident(field_ty, &param, &target);
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.extend([
+        // bound for the generic param that is being coerced
+        syn::parse_quote!(#param: ::unsizing_experiments::unsize::FromMetadataUnsize<#target>),
+        // bound for the type of the last field that is being coerced
+        syn::parse_quote!(#field_ty: ::unsizing_experiments::unsize::FromMetadataUnsize<#target_field_ty>),
+        // demand that the metadata of the struct is the same as its last field
+        syn::parse_quote!(#source_ty: ::core::ptr::Pointee<Metadata = <#field_ty as ::core::ptr::Pointee>::Metadata>),
+        syn::parse_quote!(#target_ty: ::core::ptr::Pointee<Metadata = <#target_field_ty as ::core::ptr::Pointee>::Metadata>),
+    ] as [syn::WherePredicate; 4]);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        // SAFETY: Only the last field of the struct involves the unsized parameter, so the struct
+        // is laid out like its last field as far as the metadata is concerned
+        unsafe impl #impl_generics ::unsizing_experiments::unsize::FromMetadataUnsize<#target_ty>
+            for #source_ty #where_clause
+        {
+            fn target_metadata(
+                metadata: <Self as ::core::ptr::Pointee>::Metadata,
+            ) -> <#target_ty as ::core::ptr::Pointee>::Metadata {
+                <#field_ty as ::unsizing_experiments::unsize::FromMetadataUnsize<#target_field_ty>>::target_metadata(metadata)
+            }
+        }
+    })
+}
+
+/// The arguments of a struct level `#[unsize(unsafe(target = [T], data = xs, len = len))]`.
+struct Projection {
+    target: Type,
+    data: Member,
+    len: Member,
+}
+
+fn parse_projection(attr: &Attribute) -> syn::Result<Projection> {
+    attr.parse_args_with(|input: ParseStream| {
+        // the derive relies on invariants of the struct it cannot check, so demand an explicit `unsafe`
+        input.parse::<Token![unsafe]>().map_err(|error| {
+            syn::Error::new(
+                error.span(),
+                "the field projection has to be wrapped in `unsafe(...)`, \
+                 as the first `len` elements of `data` have to be valid for the target",
+            )
+        })?;
+        let content;
+        syn::parenthesized!(content in input);
+        let (mut target, mut data, mut len) = (None, None, None);
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            content.parse::<Token![=]>()?;
+            if key == "target" {
+                target = Some(content.parse::<Type>()?);
+            } else if key == "data" {
+                data = Some(content.parse::<Member>()?);
+            } else if key == "len" {
+                len = Some(content.parse::<Member>()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "expected one of `target`, `data` or `len`",
+                ));
+            }
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        let missing = |key| syn::Error::new_spanned(attr, format!("missing `{key} = ...`"));
+        Ok(Projection {
+            target: target.ok_or_else(|| missing("target"))?,
+            data: data.ok_or_else(|| missing("data"))?,
+            len: len.ok_or_else(|| missing("len"))?,
+        })
+    })
+}
+
+/// Derives an `Unsize` impl for structs storing their elements inline in an array field, with the
+/// number of valid elements being stored in another field.
+fn expand_projection(
+    input: &DeriveInput,
+    data: &DataStruct,
+    attr: &Attribute,
+) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let Projection {
+        target,
+        data: data_member,
+        len,
+    } = parse_projection(attr)?;
+
+    let target_elem = match &target {
+        Type::Slice(slice) => slice.elem.as_ref().clone(),
+        Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => {
+            syn::parse_quote!(u8)
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &target,
+                "the target has to be a slice `[T]` or `str`",
+            ))
+        }
+    };
+    let find_field = |member: &Member| {
+        data.fields
+            .members()
+            .zip(&data.fields)
+            .find(|(m, _)| m == member)
+            .map(|(_, field)| field)
+            .ok_or_else(|| syn::Error::new_spanned(member, "no such field"))
+    };
+    let data_field = find_field(&data_member)?;
+    find_field(&len)?;
+    let Type::Array(array) = &data_field.ty else {
+        return Err(syn::Error::new_spanned(
+            &data_field.ty,
+            "the `data` field has to be an array",
+        ));
+    };
+    let data_elem = &array.elem;
+    let capacity = &array.len;
+
+    let generics = strip_param_attrs(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        // SAFETY: The `unsafe` in the attribute asserts that the first `len` elements of the array
+        // are valid for the target, the length is checked against the capacity of the array
+        unsafe impl #impl_generics ::unsizing_experiments::unsize::Unsize<#target>
+            for #name #ty_generics #where_clause
+        {
+            unsafe fn target_metadata(
+                self: *const Self,
+            ) -> <#target as ::core::ptr::Pointee>::Metadata {
+                const {
+                    assert!(
+                        ::core::mem::size_of::<#data_elem>() == ::core::mem::size_of::<#target_elem>()
+                            && ::core::mem::align_of::<#data_elem>() == ::core::mem::align_of::<#target_elem>(),
+                        "the elements of `data` have to be layout compatible with the target's elements",
+                    )
+                };
+                // SAFETY: self points to a live Self as per calling contract
+                let len = unsafe { (*self).#len };
+                assert!(len <= #capacity, "`len` exceeds the capacity of `data`");
+                len
+            }
+
+            unsafe fn target_data_address(self: *const Self) -> *const () {
+                // SAFETY: self points to a live Self as per calling contract
+                unsafe { ::core::ptr::addr_of!((*self).#data_member) }.cast()
+            }
+        }
+
+        // SAFETY: The same projection as for `Unsize::target_data_address`
+        unsafe impl #impl_generics ::unsizing_experiments::unsize::UnsizeMut<#target>
+            for #name #ty_generics #where_clause
+        {
+            unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+                // SAFETY: self points to a live Self as per calling contract
+                unsafe { ::core::ptr::addr_of_mut!((*self).#data_member) }.cast()
+            }
+        }
+    })
+}
@@ -0,0 +1,129 @@
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::{format_ident, ToTokens};
+use syn::{GenericParam, Generics, Type, TypeParam, WherePredicate};
+
+/// Whether the tokens mention the given identifier anywhere.
+pub fn mentions(tokens: impl ToTokens, ident: &Ident) -> bool {
+    fn inner(tokens: TokenStream, ident: &Ident) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree::Ident(i) => i == *ident,
+            TokenTree::Group(g) => inner(g.stream(), ident),
+            _ => false,
+        })
+    }
+    inner(tokens.into_token_stream(), ident)
+}
+
+/// Replaces all occurrences of the identifier `from` with `to`.
+pub fn replace_ident(tokens: impl ToTokens, from: &Ident, to: &Ident) -> TokenStream {
+    fn inner(tokens: TokenStream, from: &Ident, to: &Ident) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|tt| match tt {
+                TokenTree::Ident(i) if i == *from => TokenTree::Ident(to.clone()),
+                TokenTree::Group(g) => {
+                    let mut new = Group::new(g.delimiter(), inner(g.stream(), from, to));
+                    new.set_span(g.span());
+                    TokenTree::Group(new)
+                }
+                tt => tt,
+            })
+            .collect()
+    }
+    inner(tokens.into_token_stream(), from, to)
+}
+
+/// Whether the type is (syntactically) a `PhantomData`.
+pub fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Finds the type parameter that is being unsized, which is either marked with `#[attr]` or the
+/// only type parameter of the type.
+pub fn unsized_param(generics: &Generics, attr: &str) -> syn::Result<Ident> {
+    let mut marked = generics.type_params().filter(|param| {
+        param
+            .attrs
+            .iter()
+            .any(|a| a.path().is_ident(attr) && matches!(a.meta, syn::Meta::Path(_)))
+    });
+    if let Some(param) = marked.next() {
+        if let Some(other) = marked.next() {
+            return Err(syn::Error::new_spanned(
+                other,
+                format!("only a single type parameter can be marked with `#[{attr}]`"),
+            ));
+        }
+        return Ok(param.ident.clone());
+    }
+    let mut params = generics.type_params();
+    match (params.next(), params.next()) {
+        (Some(param), None) => Ok(param.ident.clone()),
+        (None, _) => Err(syn::Error::new_spanned(
+            generics,
+            "expected a type parameter to unsize",
+        )),
+        (Some(_), Some(_)) => Err(syn::Error::new_spanned(
+            generics,
+            format!("mark the type parameter to unsize with `#[{attr}]`"),
+        )),
+    }
+}
+
+/// The fresh type parameter standing in for the target of the unsizing.
+pub fn target_param(param: &Ident) -> Ident {
+    format_ident!("__{}Target", param)
+}
+
+/// Strips all attributes of the generic parameters, so they can be used in the generated impl.
+pub fn strip_param_attrs(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        match param {
+            GenericParam::Type(param) => param.attrs.clear(),
+            GenericParam::Lifetime(param) => param.attrs.clear(),
+            GenericParam::Const(param) => param.attrs.clear(),
+        }
+    }
+    generics
+}
+
+/// Adds the parameter `to` to the generics, carrying over all bounds of the parameter `from` that
+/// are required for the target type to be well-formed.
+pub fn add_target_param(generics: &mut Generics, from: &Ident, to: &Ident) -> syn::Result<()> {
+    let param = generics
+        .type_params()
+        .find(|param| param.ident == *from)
+        .expect("parameter is part of the generics");
+    let mut target = param.clone();
+    target.attrs.clear();
+    target.eq_token = None;
+    target.default = None;
+    let target: TypeParam = syn::parse2(replace_ident(target, from, to))?;
+    generics.params.push(GenericParam::Type(target));
+
+    let mut target_predicates = Vec::new();
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let WherePredicate::Type(predicate) = predicate {
+                if mentions(&predicate.bounded_ty, from) {
+                    target_predicates.push(syn::parse2::<WherePredicate>(replace_ident(
+                        predicate, from, to,
+                    ))?);
+                }
+            }
+        }
+    }
+    generics
+        .make_where_clause()
+        .predicates
+        .extend(target_predicates);
+    Ok(())
+}
@@ -2,15 +2,29 @@
 //! deconstructing and constructing the pointers participating in the coercion to user code instead
 //! of having the compiler do it magically.
 use core::alloc::Allocator;
-use core::cell::Cell;
+use core::cell::{Cell, OnceCell, RefCell, UnsafeCell};
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
 use core::pin::Pin;
-use core::ptr;
+use core::ptr::{self, NonNull};
+use core::str::Utf8Error;
 
 use alloc::boxed::Box;
-use alloc::sync::Arc;
+use alloc::rc::{self, Rc};
+use alloc::string::String;
+use alloc::sync::{self, Arc};
+use alloc::vec::Vec;
 
-use crate::unsize::{FromMetadataUnsize, Unsize};
+use crate::pointer::{Pointer, Rebind};
+use crate::unsize::{
+    FromMetadataTrySize, FromMetadataUnsize, TrySize, TrySizeOwned, TryUnsize, TryUnsizeMut,
+    TryUnsizeOwned, Unsize, UnsizeMut,
+};
 use crate::TypedMetadata;
+
+pub use unsizing_experiments_derive::CoerceUnsized;
+
 /// Trait that indicates that this is a pointer or a wrapper for one,
 /// where unsizing can be performed on the pointee.
 ///
@@ -65,15 +79,18 @@ pub trait CoerceUnsized<Target> {
  */
 
 // &mut T -> &mut U
-impl<'a, T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<&'a mut U> for &'a mut T {
+// Note the use of UnsizeMut! The data address has to be derived from a mutable pointer to be
+// allowed to write through it
+impl<'a, T: ?Sized + UnsizeMut<U>, U: ?Sized> CoerceUnsized<&'a mut U> for &'a mut T {
     fn coerce_unsized(self) -> &'a mut U {
+        // SAFETY: self is a reference
+        let metadata = unsafe { Unsize::target_metadata(&*self) };
         // SAFETY: the returned fat pointer must be valid according to [`Unsize`]
         unsafe {
             &mut *ptr::from_raw_parts_mut(
                 // SAFETY: self is a reference
-                Unsize::target_data_address(self).cast_mut(),
-                // SAFETY: self is a reference
-                Unsize::target_metadata(self),
+                UnsizeMut::target_data_address_mut(self),
+                metadata,
             )
         }
     }
@@ -95,13 +112,14 @@ impl<'a, 'b: 'a, T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<&'a U> for &'b
 }
 
 // &mut T -> *mut U
-impl<'a, T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<*mut U> for &'a mut T {
+impl<'a, T: ?Sized + UnsizeMut<U>, U: ?Sized> CoerceUnsized<*mut U> for &'a mut T {
     fn coerce_unsized(self) -> *mut U {
+        // SAFETY: self is a reference
+        let metadata = unsafe { Unsize::target_metadata(&*self) };
+        // SAFETY: self is a reference
         ptr::from_raw_parts_mut(
-            // SAFETY: self is a reference
-            unsafe { Unsize::target_data_address(self) }.cast_mut(),
-            // SAFETY: self is a reference
-            unsafe { Unsize::target_metadata(self) },
+            unsafe { UnsizeMut::target_data_address_mut(self) },
+            metadata,
         )
     }
 }
@@ -145,14 +163,21 @@ impl<'a, T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<*const U> for &'a T {
     }
 }
 
-// *mut T -> *mut U
-// Note the use of FromMetadataUnsize! We can't deref the pointer as we do not know whether it is live
-impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> CoerceUnsized<*mut U> for *mut T {
-    fn coerce_unsized(self) -> *mut U {
-        ptr::from_raw_parts_mut(
-            self.cast(),
-            <T as FromMetadataUnsize<U>>::target_metadata(core::ptr::metadata(self)),
-        )
+// &T -> NonNull<U>
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<NonNull<U>> for &T {
+    fn coerce_unsized(self) -> NonNull<U> {
+        let ptr: *const U = self.coerce_unsized();
+        // SAFETY: The pointer is valid for creating a reference as per [`Unsize`], so it is non-null
+        unsafe { NonNull::new_unchecked(ptr.cast_mut()) }
+    }
+}
+
+// &mut T -> NonNull<U>
+impl<T: ?Sized + UnsizeMut<U>, U: ?Sized> CoerceUnsized<NonNull<U>> for &mut T {
+    fn coerce_unsized(self) -> NonNull<U> {
+        let ptr: *mut U = self.coerce_unsized();
+        // SAFETY: The pointer is valid for creating a reference as per [`Unsize`], so it is non-null
+        unsafe { NonNull::new_unchecked(ptr) }
     }
 }
 
@@ -167,62 +192,522 @@ impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> CoerceUnsized<*const U> for *
     }
 }
 
+/// Coercion of raw pointers to objects that are known to be live, allowing the use of the more
+/// permissive [`Unsize`] instead of [`FromMetadataUnsize`].
+pub trait CoerceUnsizedLive<Target> {
+    /// # Safety
+    ///
+    /// `self` must point to a live and valid instance of its pointee. The returned pointer is only
+    /// valid for as long as the parts of the pointee that its metadata and data address were read
+    /// from are not modified, for example by a `Vec` reallocating.
+    unsafe fn coerce_unsized_live(self) -> Target;
+}
+
 // *const T -> *const U
-// Note the use of FromMetadataUnsize! We can't deref the pointer as we do not know whether it is live
-impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> CoerceUnsized<*const U> for *const T {
-    fn coerce_unsized(self) -> *const U {
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsizedLive<*const U> for *const T {
+    unsafe fn coerce_unsized_live(self) -> *const U {
         ptr::from_raw_parts(
-            self.cast(),
-            <T as FromMetadataUnsize<U>>::target_metadata(core::ptr::metadata(self)),
+            // SAFETY: self points to a live object as per calling contract
+            unsafe { Unsize::target_data_address(self) },
+            // SAFETY: self points to a live object as per calling contract
+            unsafe { Unsize::target_metadata(self) },
+        )
+    }
+}
+
+// *mut T -> *mut U
+impl<T: ?Sized + UnsizeMut<U>, U: ?Sized> CoerceUnsizedLive<*mut U> for *mut T {
+    unsafe fn coerce_unsized_live(self) -> *mut U {
+        // SAFETY: self points to a live object as per calling contract
+        let metadata = unsafe { Unsize::target_metadata(self) };
+        // SAFETY: self points to a live object as per calling contract
+        ptr::from_raw_parts_mut(
+            unsafe { UnsizeMut::target_data_address_mut(self) },
+            metadata,
         )
     }
 }
 
+// *mut T -> *const U
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsizedLive<*const U> for *mut T {
+    unsafe fn coerce_unsized_live(self) -> *const U {
+        // SAFETY: forwarded calling contract
+        unsafe { self.cast_const().coerce_unsized_live() }
+    }
+}
+
+// NonNull<T> -> NonNull<U>
+impl<T: ?Sized + UnsizeMut<U>, U: ?Sized> CoerceUnsizedLive<NonNull<U>> for NonNull<T> {
+    unsafe fn coerce_unsized_live(self) -> NonNull<U> {
+        // SAFETY: forwarded calling contract
+        let coerced: *mut U = unsafe { self.as_ptr().coerce_unsized_live() };
+        // SAFETY: The data address of a live object is non-null, as the reference impls of
+        // `CoerceUnsized` turn it into a reference
+        unsafe { NonNull::new_unchecked(coerced) }
+    }
+}
+
 /*
  * Some more interesting implementations
  */
 
-// Box<T> -> Box<U>
-// Note the use of FromMetadataUnsize! unstable unsize would be unsound as the box is owning!
-impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized, A: Allocator> CoerceUnsized<Box<U, A>>
+/// Coerces a [`Pointer`] by mapping the metadata of its pointee through [`FromMetadataUnsize`],
+/// which is all the `CoerceUnsized` impls of most pointers do.
+///
+/// This can't be a blanket `CoerceUnsized` impl, as downstream crates could implement [`Pointer`]
+/// for references to their own types, which coherence can only rule out with negative reasoning.
+/// So every pointer implements `CoerceUnsized` itself by calling this, custom pointers included.
+pub fn coerce_pointer<P, Q>(pointer: P) -> Q
+where
+    P: Rebind<Q::Pointee, Output = Q, Extra = Q::Extra>,
+    Q: Pointer,
+    P::Pointee: FromMetadataUnsize<Q::Pointee>,
+{
+    let (data_address, metadata, extra) = pointer.into_raw_parts();
+    // SAFETY: According to [`FromMetadataUnsize`] the metadata is valid for the object at
+    // `data_address` and the pointees are layout compatible, and `Q` is `P` rebound to another
+    // pointee
+    unsafe {
+        Q::from_raw_parts(
+            data_address,
+            <P::Pointee as FromMetadataUnsize<Q::Pointee>>::target_metadata(metadata),
+            extra,
+        )
+    }
+}
+
+// *mut T -> *mut U, *const T -> *const U, NonNull<T> -> NonNull<U>, Box<T> -> Box<U>, Rc<T> -> Rc<U>,
+// Arc<T> -> Arc<U> and their weak counterparts
+// Note the use of FromMetadataUnsize! We can't deref raw pointers as we do not know whether they
+// are live, and unstable unsize would be unsound for the owning pointers, as they rely on the data
+// pointer pointing to their allocation.
+macro_rules! impl_coerce_unsized_pointer {
+    ($(<$($param:ident $(: $bound:path)?),*> $ptr:ty => $target:ty;)*) => {
+        $(
+            impl<$($param $(: $bound)?,)* T: ?Sized + FromMetadataUnsize<U>, U: ?Sized>
+                CoerceUnsized<$target> for $ptr
+            {
+                fn coerce_unsized(self) -> $target {
+                    coerce_pointer(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_coerce_unsized_pointer! {
+    <> *const T => *const U;
+    <> *mut T => *mut U;
+    <> NonNull<T> => NonNull<U>;
+    <A: Allocator> Box<T, A> => Box<U, A>;
+    <A: Allocator> Rc<T, A> => Rc<U, A>;
+    <A: Allocator> Arc<T, A> => Arc<U, A>;
+    <A: Allocator> rc::Weak<T, A> => rc::Weak<U, A>;
+    <A: Allocator> sync::Weak<T, A> => sync::Weak<U, A>;
+}
+
+// Box<str> -> Box<[u8]>, Rc<str> -> Rc<[u8]>, Arc<str> -> Arc<[u8]>
+// These can't go through `coerce_pointer`, as `str` does not implement
+// `FromMetadataUnsize<[u8]>`, see its `Unsize` impl. Unlike a `&mut str`, these pointers give up
+// their `str`, and `Rc` and `Arc` only hand out mutable access once they are unique.
+macro_rules! impl_coerce_unsized_str_bytes {
+    ($($ptr:ident),*) => {
+        $(
+            impl<A: Allocator> CoerceUnsized<$ptr<[u8], A>> for $ptr<str, A> {
+                fn coerce_unsized(self) -> $ptr<[u8], A> {
+                    let (data_address, len, a) = self.into_raw_parts();
+                    // SAFETY: `str` has the same layout as `[u8]` with the same length metadata
+                    unsafe { Pointer::from_raw_parts(data_address, len, a) }
+                }
+            }
+        )*
+    };
+}
+
+impl_coerce_unsized_str_bytes!(Box, Rc, Arc);
+
+// Vec<T> -> Box<[T]>, Vec<T> -> Rc<[T]>, Vec<T> -> Arc<[T]> and the same for String and str
+// Unlike the compiler's coercions these may allocate. A box reuses the buffer of the vector, which
+// is only reallocated if it has spare capacity. `Rc` and `Arc` store their reference counts in
+// front of the value, so they always allocate, but only once as the elements are moved directly.
+impl<T, A: Allocator> CoerceUnsized<Box<[T], A>> for Vec<T, A> {
+    fn coerce_unsized(self) -> Box<[T], A> {
+        self.into_boxed_slice()
+    }
+}
+
+impl<T, A: Allocator> CoerceUnsized<Rc<[T], A>> for Vec<T, A> {
+    fn coerce_unsized(self) -> Rc<[T], A> {
+        Rc::from(self)
+    }
+}
+
+impl<T, A: Allocator + Clone> CoerceUnsized<Arc<[T], A>> for Vec<T, A> {
+    fn coerce_unsized(self) -> Arc<[T], A> {
+        Arc::from(self)
+    }
+}
+
+impl CoerceUnsized<Box<str>> for String {
+    fn coerce_unsized(self) -> Box<str> {
+        self.into_boxed_str()
+    }
+}
+
+impl CoerceUnsized<Rc<str>> for String {
+    fn coerce_unsized(self) -> Rc<str> {
+        Rc::from(self)
+    }
+}
+
+impl CoerceUnsized<Arc<str>> for String {
+    fn coerce_unsized(self) -> Arc<str> {
+        Arc::from(self)
+    }
+}
+
+/// Reinterprets a pointer to bytes as a pointer to a `str` if the bytes are valid UTF-8, the
+/// checked reverse of the `str -> [u8]` coercions. On failure the pointer is handed back.
+///
+/// Pointers to byte arrays can be coerced to pointers to byte slices first.
+pub fn str_from_utf8<P>(bytes: P) -> Result<P::Output, (P, Utf8Error)>
+where
+    P: Rebind<str, Pointee = [u8]> + Deref<Target = [u8]>,
+{
+    try_coerce_pointer(bytes)
+}
+
+/// The fallible counterpart of [`CoerceUnsized`], for coercions that check at runtime whether the
+/// pointee is valid for the target, see [`TryUnsize`].
+///
+/// Every pointer that can be coerced infallibly can be coerced through this trait as well, with
+/// [`Infallible`](core::convert::Infallible) as its error.
+pub trait TryCoerceUnsized<Target>: Sized {
+    /// The error returned if the pointee is not valid for the target.
+    type Error;
+
+    /// Tries to coerce the pointer, handing it back along with the error on failure.
+    fn try_coerce_unsized(self) -> Result<Target, (Self, Self::Error)>;
+}
+
+// &'a T -> &'a U
+impl<'a, T: ?Sized + TryUnsize<U>, U: ?Sized> TryCoerceUnsized<&'a U> for &'a T {
+    type Error = <T as TryUnsize<U>>::Error;
+
+    fn try_coerce_unsized(self) -> Result<&'a U, (Self, Self::Error)> {
+        // SAFETY: The pointer is derived from a reference
+        let metadata = match unsafe { TryUnsize::try_target_metadata(self as *const T) } {
+            Ok(metadata) => metadata,
+            Err(error) => return Err((self, error)),
+        };
+        // SAFETY: According to [`TryUnsize`] the metadata is valid for the object at the address
+        Ok(unsafe {
+            &*ptr::from_raw_parts(<T as TryUnsize<U>>::target_data_address(self), metadata)
+        })
+    }
+}
+
+// &'a mut T -> &'a mut U
+impl<'a, T: ?Sized + TryUnsizeMut<U>, U: ?Sized> TryCoerceUnsized<&'a mut U> for &'a mut T {
+    type Error = <T as TryUnsize<U>>::Error;
+
+    fn try_coerce_unsized(self) -> Result<&'a mut U, (Self, Self::Error)> {
+        // SAFETY: The pointer is derived from a reference
+        let metadata = match unsafe { TryUnsize::try_target_metadata(&*self as *const T) } {
+            Ok(metadata) => metadata,
+            Err(error) => return Err((self, error)),
+        };
+        // SAFETY: According to [`TryUnsizeMut`] the metadata is valid for the object at the
+        // address, which permits writes
+        Ok(unsafe {
+            &mut *ptr::from_raw_parts_mut(
+                <T as TryUnsizeMut<U>>::target_data_address_mut(self),
+                metadata,
+            )
+        })
+    }
+}
+
+type TryCoercePointerResult<P, Q> = Result<
+    Q,
+    (
+        P,
+        <<P as Pointer>::Pointee as TryUnsize<<Q as Pointer>::Pointee>>::Error,
+    ),
+>;
+
+// Unlike for `CoerceUnsized`, the pointee is required to be live, as it is inspected for validity.
+fn try_coerce_pointer<P, Q>(pointer: P) -> TryCoercePointerResult<P, Q>
+where
+    P: Rebind<Q::Pointee, Output = Q, Extra = Q::Extra> + Deref<Target = <P as Pointer>::Pointee>,
+    Q: Pointer,
+    P::Pointee: TryUnsizeOwned<Q::Pointee>,
+{
+    // SAFETY: The pointer is derived from a reference
+    let metadata = match unsafe { TryUnsize::try_target_metadata(&*pointer as *const P::Pointee) } {
+        Ok(metadata) => metadata,
+        Err(error) => return Err((pointer, error)),
+    };
+    let (data_address, _, extra) = pointer.into_raw_parts();
+    // SAFETY: According to [`TryUnsizeOwned`] the pointee is unsized in place, the metadata is
+    // valid for it and the pointees are layout compatible, and `Q` is `P` rebound to another
+    // pointee
+    Ok(unsafe { Q::from_raw_parts(data_address, metadata, extra) })
+}
+
+// Box<T> -> Box<U>, Rc<T> -> Rc<U>, Arc<T> -> Arc<U>
+macro_rules! impl_try_coerce_unsized_pointer {
+    ($($ptr:ident),*) => {
+        $(
+            impl<T: ?Sized + TryUnsizeOwned<U>, U: ?Sized, A: Allocator>
+                TryCoerceUnsized<$ptr<U, A>> for $ptr<T, A>
+            {
+                type Error = <T as TryUnsize<U>>::Error;
+
+                fn try_coerce_unsized(self) -> Result<$ptr<U, A>, (Self, Self::Error)> {
+                    try_coerce_pointer(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_coerce_unsized_pointer!(Box, Rc, Arc);
+
+/// The inverse of [`CoerceUnsized`], turning a pointer back into a pointer to a type that unsizes
+/// to its pointee, after checking that the pointee is valid for it, see [`TrySize`].
+pub trait Resize<Target>: Sized {
+    /// Tries to resize the pointer, handing it back if its pointee is not valid for the target.
+    fn try_resize(self) -> Result<Target, Self>;
+}
+
+// &'a T -> &'a U
+impl<'a, T: ?Sized + TrySize<U>, U: ?Sized> Resize<&'a U> for &'a T {
+    fn try_resize(self) -> Result<&'a U, Self> {
+        // SAFETY: The pointer is derived from a reference
+        match unsafe { TrySize::try_target_metadata(self as *const T) } {
+            Some(metadata) => {
+                // SAFETY: According to [`TrySize`] the object is a valid `U` with that metadata
+                Ok(unsafe { &*ptr::from_raw_parts((self as *const T).cast(), metadata) })
+            }
+            None => Err(self),
+        }
+    }
+}
+
+// &'a mut T -> &'a mut U
+impl<'a, T: ?Sized + TrySize<U>, U: ?Sized> Resize<&'a mut U> for &'a mut T {
+    fn try_resize(self) -> Result<&'a mut U, Self> {
+        // SAFETY: The pointer is derived from a reference
+        match unsafe { TrySize::try_target_metadata(&*self as *const T) } {
+            Some(metadata) => {
+                // SAFETY: According to [`TrySize`] the object is a valid `U` with that metadata,
+                // and writing a valid `U` leaves a valid `T` behind
+                Ok(unsafe { &mut *ptr::from_raw_parts_mut((self as *mut T).cast(), metadata) })
+            }
+            None => Err(self),
+        }
+    }
+}
+
+fn try_resize_pointer<P, Q>(pointer: P) -> Result<Q, P>
+where
+    P: Rebind<Q::Pointee, Output = Q, Extra = Q::Extra> + Deref<Target = <P as Pointer>::Pointee>,
+    Q: Pointer,
+    P::Pointee: TrySizeOwned<Q::Pointee>,
+{
+    // SAFETY: The pointer is derived from a reference
+    match unsafe { TrySize::try_target_metadata(&*pointer as *const P::Pointee) } {
+        Some(metadata) => {
+            let (data_address, _, extra) = pointer.into_raw_parts();
+            // SAFETY: According to [`TrySizeOwned`] the pointee is a valid `Q::Pointee` with that
+            // metadata and layout compatible with it, and `Q` is `P` rebound to it
+            Ok(unsafe { Q::from_raw_parts(data_address, metadata, extra) })
+        }
+        None => Err(pointer),
+    }
+}
+
+// Box<T> -> Box<U>, Rc<T> -> Rc<U>, Arc<T> -> Arc<U>
+macro_rules! impl_resize_pointer {
+    ($($ptr:ident),*) => {
+        $(
+            impl<T: ?Sized + TrySizeOwned<U>, U: ?Sized, A: Allocator> Resize<$ptr<U, A>>
+                for $ptr<T, A>
+            {
+                fn try_resize(self) -> Result<$ptr<U, A>, Self> {
+                    try_resize_pointer(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_resize_pointer!(Box, Rc, Arc);
+
+fn resize_pointer_from_metadata<P, Q>(pointer: P) -> Result<Q, P>
+where
+    P: Rebind<Q::Pointee, Output = Q, Extra = Q::Extra>,
+    Q: Pointer,
+    P::Pointee: FromMetadataTrySize<Q::Pointee>,
+{
+    let (data_address, metadata, extra) = pointer.into_raw_parts();
+    match <P::Pointee as FromMetadataTrySize<Q::Pointee>>::try_target_metadata_from(metadata) {
+        Some(target_metadata) => {
+            // SAFETY: According to [`FromMetadataTrySize`] the pointee is a valid `Q::Pointee`
+            // with that metadata and layout compatible with it, and `Q` is `P` rebound to it
+            Ok(unsafe { Q::from_raw_parts(data_address, target_metadata, extra) })
+        }
+        // SAFETY: The parts are passed back unchanged
+        None => Err(unsafe { P::from_raw_parts(data_address, metadata, extra) }),
+    }
+}
+
+// *const T -> *const U, *mut T -> *mut U, NonNull<T> -> NonNull<U>, Weak<T> -> Weak<U>
+// These can't inspect their pointee, it may be dangling or already dropped, so they are resized
+// based on the metadata alone.
+macro_rules! impl_resize_pointer_from_metadata {
+    ($(<$($param:ident $(: $bound:path)?),*> $ptr:ty => $target:ty;)*) => {
+        $(
+            impl<$($param $(: $bound)?,)* T: ?Sized + FromMetadataTrySize<U>, U: ?Sized>
+                Resize<$target> for $ptr
+            {
+                fn try_resize(self) -> Result<$target, Self> {
+                    resize_pointer_from_metadata(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_resize_pointer_from_metadata! {
+    <> *const T => *const U;
+    <> *mut T => *mut U;
+    <> NonNull<T> => NonNull<U>;
+    <A: Allocator> rc::Weak<T, A> => rc::Weak<U, A>;
+    <A: Allocator> sync::Weak<T, A> => sync::Weak<U, A>;
+}
+
+/// Pointers whose pinning guarantees are preserved by their [`Resize`] impls to `Target`, allowing
+/// `Pin<Self>` to be resized to `Pin<Target>` as well, the counterpart of [`PinCoerceUnsized`].
+///
+/// # Safety
+///
+/// The `Deref` and `DerefMut` impls of `Self` and `Target` must return a reference to the same
+/// object, and resizing the pointer via [`Resize`] must not move that object.
+pub unsafe trait PinResize<Target: Deref>: Deref {}
+
+// SAFETY: The Deref impl of references is the identity, and `TrySize` sizes in place
+unsafe impl<'a, T: ?Sized + TrySize<U>, U: ?Sized> PinResize<&'a U> for &'a T {}
+// SAFETY: The Deref and DerefMut impls of references are the identity, and `TrySize` sizes in place
+unsafe impl<'a, T: ?Sized + TrySize<U>, U: ?Sized> PinResize<&'a mut U> for &'a mut T {}
+// SAFETY: The Deref and DerefMut impls of `Box` return its allocation, which the resizing via
+// `TrySizeOwned` keeps. As for `PinCoerceUnsized`, the bound excludes downstream `Resize` impls
+// between boxes of their own types.
+unsafe impl<T: ?Sized + TrySizeOwned<U>, U: ?Sized, A: Allocator> PinResize<Box<U, A>>
     for Box<T, A>
 {
-    fn coerce_unsized(self) -> Box<U, A> {
-        let (this, a) = Box::into_raw_with_allocator(self);
-        // SAFETY: According to [`FromMetadataUnsize`] the metadata is associated with our pointer
+}
+// SAFETY: The Deref impl of `Rc` returns its allocation, which the resizing via `TrySizeOwned`
+// keeps
+unsafe impl<T: ?Sized + TrySizeOwned<U>, U: ?Sized, A: Allocator> PinResize<Rc<U, A>> for Rc<T, A> {}
+// SAFETY: The Deref impl of `Arc` returns its allocation, which the resizing via `TrySizeOwned`
+// keeps
+unsafe impl<T: ?Sized + TrySizeOwned<U>, U: ?Sized, A: Allocator> PinResize<Arc<U, A>>
+    for Arc<T, A>
+{
+}
+// SAFETY: `Pin` delegates to the Deref and DerefMut impls of its pointer
+unsafe impl<P: PinResize<U>, U: Deref> PinResize<Pin<U>> for Pin<P> {}
+
+impl<P, U> Resize<Pin<U>> for Pin<P>
+where
+    P: Resize<U> + PinResize<U>,
+    U: Deref,
+{
+    fn try_resize(self) -> Result<Pin<U>, Self> {
+        // SAFETY: The pointee stays pinned, as resizing a `PinResize` pointer does not move it and
+        // the target's Deref impls return the very same object. On failure the pointer is handed
+        // back unchanged.
         unsafe {
-            Box::from_raw_in(
-                ptr::from_raw_parts_mut(
-                    this.cast(),
-                    // SAFETY: this is derived from the box which is currently live
-                    <T as FromMetadataUnsize<U>>::target_metadata(ptr::metadata(this)),
-                ),
-                a,
-            )
+            match Pin::into_inner_unchecked(self).try_resize() {
+                Ok(pointer) => Ok(Pin::new_unchecked(pointer)),
+                Err(pointer) => Err(Pin::new_unchecked(pointer)),
+            }
         }
     }
 }
 
-// FIXME: This impl is unsound!
+/// Pointers whose pinning guarantees are preserved by their `CoerceUnsized` impls to `Target`,
+/// allowing `Pin<Self>` to be coerced to `Pin<Target>` as well.
+///
+/// This is required as coercing a pointer may change which `Deref` impl is used, see
+/// [rust-lang/rust#68015](https://github.com/rust-lang/rust/issues/68015). It is generic over the
+/// target, as a coercion may also project to a different object than the one that is pinned. A
+/// `&mut Vec<T>` coerces to a `&mut [T]` of its buffer, which moves once the vector grows, so
+/// references only implement this for in-place coercions via [`FromMetadataUnsize`].
+///
+/// # Safety
+///
+/// The `Deref` and `DerefMut` impls of `Self` and `Target` must return a reference to the same
+/// object, and coercing the pointer via [`CoerceUnsized`] must not move that object.
+pub unsafe trait PinCoerceUnsized<Target: Deref>: Deref {}
+
+// SAFETY: The Deref impl of references is the identity, and `FromMetadataUnsize` unsizes in place
+unsafe impl<'a, 'b: 'a, T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> PinCoerceUnsized<&'a U>
+    for &'b T
+{
+}
+// SAFETY: The Deref and DerefMut impls of references are the identity, and `FromMetadataUnsize`
+// unsizes in place
+unsafe impl<'a, 'b: 'a, T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> PinCoerceUnsized<&'a U>
+    for &'b mut T
+{
+}
+// SAFETY: The Deref and DerefMut impls of references are the identity, and `FromMetadataUnsize`
+// unsizes in place
+unsafe impl<'a, T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> PinCoerceUnsized<&'a mut U>
+    for &'a mut T
+{
+}
+// SAFETY: The Deref and DerefMut impls of `Box` return its allocation, which the coercion via
+// `FromMetadataUnsize` keeps. Downstream crates can implement `CoerceUnsized` between boxes of
+// their own types, as `Box` is `#[fundamental]`, so the bound excludes any such impl.
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized, A: Allocator> PinCoerceUnsized<Box<U, A>>
+    for Box<T, A>
+{
+}
+// SAFETY: The Deref impl of `Rc` returns its allocation, which the coercion via
+// `FromMetadataUnsize` keeps
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized, A: Allocator> PinCoerceUnsized<Rc<U, A>>
+    for Rc<T, A>
+{
+}
+// SAFETY: The Deref impl of `Arc` returns its allocation, which the coercion via
+// `FromMetadataUnsize` keeps
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized, A: Allocator> PinCoerceUnsized<Arc<U, A>>
+    for Arc<T, A>
+{
+}
+// SAFETY: `Pin` delegates to the Deref and DerefMut impls of its pointer
+unsafe impl<P: PinCoerceUnsized<U>, U: Deref> PinCoerceUnsized<Pin<U>> for Pin<P> {}
+
 // Copied from core library docs:
 // Note: this means that any impl of `CoerceUnsized` that allows coercing from
 // a type that impls `Deref<Target=impl !Unpin>` to a type that impls
 // `Deref<Target=Unpin>` is unsound. Any such impl would probably be unsound
 // for other reasons, though, so we just need to take care not to allow such
 // impls to land in std.
+// `PinCoerceUnsized` rules out such impls for the pointers involved.
 impl<P, U> CoerceUnsized<Pin<U>> for Pin<P>
 where
-    P: CoerceUnsized<U>,
-    // interesting one, we would need this for constructing the Pin via `new_unchecked`
-    // U: core::ops::Deref,
-    // it's a bit odd that this isn't required tbh, that means it is possible to construct a
-    // Pin to a raw pointer of an unsized object, even though constructing a raw pointer to a sized
-    // one is not possible!
+    P: CoerceUnsized<U> + PinCoerceUnsized<U>,
+    U: Deref,
 {
     fn coerce_unsized(self) -> Pin<U> {
-        Pin {
-            pointer: self.pointer.coerce_unsized(),
-        }
+        // SAFETY: The pointee stays pinned, as the coercion of a `PinCoerceUnsized` pointer does
+        // not move it and the target's Deref impls return the very same object
+        unsafe { Pin::new_unchecked(Pin::into_inner_unchecked(self).coerce_unsized()) }
     }
 }
 
@@ -258,22 +743,134 @@ impl<T: CoerceUnsized<U>, U> CoerceUnsized<Cell<U>> for Cell<T> {
     }
 }
 
-// Note the use of FromMetadataUnsize! unstable unsize would be unsound as arc relies on the data pointer pointing inside of the ArcInner.
-impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> CoerceUnsized<Arc<U>> for Arc<T> {
-    fn coerce_unsized(self) -> Arc<U> {
-        let ptr = Arc::into_raw(self);
+impl<T: CoerceUnsized<U>, U> CoerceUnsized<RefCell<U>> for RefCell<T> {
+    fn coerce_unsized(self) -> RefCell<U> {
+        RefCell::new(self.into_inner().coerce_unsized())
+    }
+}
 
-        // SAFETY: The arc is safe to be constructed as the pointer is unchanged
-        unsafe {
-            Arc::from_raw(ptr::from_raw_parts(
-                ptr.cast(),
-                // SAFETY: ptr is derived from a live Arc and is therefor valid
-                <T as FromMetadataUnsize<U>>::target_metadata(ptr::metadata(ptr)),
-            ))
+impl<T: CoerceUnsized<U>, U> CoerceUnsized<UnsafeCell<U>> for UnsafeCell<T> {
+    fn coerce_unsized(self) -> UnsafeCell<U> {
+        UnsafeCell::new(self.into_inner().coerce_unsized())
+    }
+}
+
+impl<T: CoerceUnsized<U>, U> CoerceUnsized<OnceCell<U>> for OnceCell<T> {
+    fn coerce_unsized(self) -> OnceCell<U> {
+        match self.into_inner() {
+            Some(value) => OnceCell::from(value.coerce_unsized()),
+            None => OnceCell::new(),
         }
     }
 }
 
+impl<T: CoerceUnsized<U>, U> CoerceUnsized<ManuallyDrop<U>> for ManuallyDrop<T> {
+    fn coerce_unsized(self) -> ManuallyDrop<U> {
+        ManuallyDrop::new(ManuallyDrop::into_inner(self).coerce_unsized())
+    }
+}
+
+// There is no data to coerce, so this works with the more permissive Unsize
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<PhantomData<U>> for PhantomData<T> {
+    fn coerce_unsized(self) -> PhantomData<U> {
+        PhantomData
+    }
+}
+
+// PhantomData<fn() -> T> -> PhantomData<fn() -> U>
+// Function pointers don't unsize themselves, but as a marker this only records that a `T` is
+// produced, which then is a `U` as well. This does not overlap with the impl above, as downstream
+// crates can't implement `Unsize` for function pointers.
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<PhantomData<fn() -> U>>
+    for PhantomData<fn() -> T>
+{
+    fn coerce_unsized(self) -> PhantomData<fn() -> U> {
+        PhantomData
+    }
+}
+
+impl<T: CoerceUnsized<U>, U> CoerceUnsized<Option<U>> for Option<T> {
+    fn coerce_unsized(self) -> Option<U> {
+        self.map(CoerceUnsized::coerce_unsized)
+    }
+}
+
+// only the Ok side is coerced, as with a derive on an enum with a single coerced field
+impl<T: CoerceUnsized<U>, U, E> CoerceUnsized<Result<U, E>> for Result<T, E> {
+    fn coerce_unsized(self) -> Result<U, E> {
+        self.map(CoerceUnsized::coerce_unsized)
+    }
+}
+
+// https://github.com/rust-lang/rfcs/pull/1792
+// &Option<T> -> Option<&U>
+// Note that these do not overlap with the reference impls, as the target is not a reference
+impl<'a, T, U: ?Sized> CoerceUnsized<Option<&'a U>> for &'a Option<T>
+where
+    &'a T: CoerceUnsized<&'a U>,
+{
+    fn coerce_unsized(self) -> Option<&'a U> {
+        self.as_ref().coerce_unsized()
+    }
+}
+
+// &mut Option<T> -> Option<&mut U>
+impl<'a, T, U: ?Sized> CoerceUnsized<Option<&'a mut U>> for &'a mut Option<T>
+where
+    &'a mut T: CoerceUnsized<&'a mut U>,
+{
+    fn coerce_unsized(self) -> Option<&'a mut U> {
+        self.as_mut().coerce_unsized()
+    }
+}
+
+// &Result<T, E> -> Result<&U, &E>
+impl<'a, T, U: ?Sized, E> CoerceUnsized<Result<&'a U, &'a E>> for &'a Result<T, E>
+where
+    &'a T: CoerceUnsized<&'a U>,
+{
+    fn coerce_unsized(self) -> Result<&'a U, &'a E> {
+        self.as_ref().coerce_unsized()
+    }
+}
+
+// &mut Result<T, E> -> Result<&mut U, &mut E>
+impl<'a, T, U: ?Sized, E> CoerceUnsized<Result<&'a mut U, &'a mut E>> for &'a mut Result<T, E>
+where
+    &'a mut T: CoerceUnsized<&'a mut U>,
+{
+    fn coerce_unsized(self) -> Result<&'a mut U, &'a mut E> {
+        self.as_mut().coerce_unsized()
+    }
+}
+
+impl<T: CoerceUnsized<U>, U, const N: usize> CoerceUnsized<[U; N]> for [T; N] {
+    fn coerce_unsized(self) -> [U; N] {
+        self.map(CoerceUnsized::coerce_unsized)
+    }
+}
+
+// implements the coercion for the tuple of all given types, then recurses with the first one dropped
+macro_rules! impl_coerce_unsized_tuple {
+    () => {};
+    ($T:ident $U:ident $t:ident $(, $Ts:ident $Us:ident $ts:ident)*) => {
+        impl<$T: CoerceUnsized<$U>, $U, $($Ts: CoerceUnsized<$Us>, $Us),*>
+            CoerceUnsized<($U, $($Us,)*)> for ($T, $($Ts,)*)
+        {
+            fn coerce_unsized(self) -> ($U, $($Us,)*) {
+                let ($t, $($ts,)*) = self;
+                ($t.coerce_unsized(), $($ts.coerce_unsized(),)*)
+            }
+        }
+        impl_coerce_unsized_tuple!($($Ts $Us $ts),*);
+    };
+}
+
+impl_coerce_unsized_tuple!(
+    T1 U1 t1, T2 U2 t2, T3 U3 t3, T4 U4 t4, T5 U5 t5, T6 U6 t6,
+    T7 U7 t7, T8 U8 t8, T9 U9 t9, T10 U10 t10, T11 U11 t11, T12 U12 t12
+);
+
 impl<T, U> CoerceUnsized<TypedMetadata<U>> for TypedMetadata<T>
 where
     T: ?Sized + FromMetadataUnsize<U>,
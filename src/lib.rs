@@ -5,16 +5,26 @@
 #![feature(
     allocator_api,
     arbitrary_self_types,
+    error_in_core,
     ptr_metadata,
     trait_upcasting,
+    tuple_trait,
+    unboxed_closures,
+    unsize,
     unsafe_pin_internals,
     strict_provenance
 )]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+// allows the derive macros to refer to this crate by name from within this crate as well
+extern crate self as unsizing_experiments;
 
 pub mod coerce_unsized;
 pub mod dispatch_from_dyn;
+pub mod pod;
+pub mod pointer;
 pub mod unsize;
 
 #[cfg(test)]
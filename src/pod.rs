@@ -0,0 +1,185 @@
+//! This module experiments with reinterpreting unsizes, viewing buffers of plain-old-data like
+//! `[u32; 4]` as the bytes `[u8]` they consist of.
+//!
+//! These can't be expressed through [`FromMetadataUnsize`](crate::unsize::FromMetadataUnsize),
+//! `[T]` and `[u8]` are not layout compatible as their alignment differs. An owning pointer like
+//! `Box<[u32]>` coerced to `Box<[u8]>` would hand its allocation back to the allocator with the
+//! wrong layout. So the byte views are implemented via [`Unsize`] and [`UnsizeMut`] only, which
+//! restricts them to references and raw pointers.
+use core::fmt;
+use core::mem;
+use core::ptr::Pointee;
+
+use crate::coerce_unsized::CoerceUnsized;
+use crate::unsize::{TrySize, TryUnsize, TryUnsizeMut, Unsize, UnsizeMut};
+
+/// Plain-old-data types whose values can be viewed as their bytes and vice versa.
+///
+/// Slices `[T]` of these types unsize to `[u8]`. Arrays `[T; N]` only do so for the primitive types
+/// implementing this trait here. As `u8` is `Pod` itself, a blanket impl for `[T; N]` would overlap
+/// with the impl of `[u8; N]`, which unsizes to `[u8]` as an array already. Arrays of other `Pod`
+/// types can be coerced to their slice first, or be passed to [`as_bytes`] and [`as_bytes_mut`].
+/// For the same reason only slices of the primitive types can be fallibly unsized back from `[u8]`
+/// via [`TryUnsize`], which checks the alignment and size of the bytes like [`try_from_bytes`]
+/// does.
+///
+/// # Safety
+///
+/// - The type must not be zero-sized and must not contain padding bytes.
+/// - Every bit pattern must be a valid value of the type.
+/// - The type must not contain an `UnsafeCell`.
+pub unsafe trait Pod: Copy + 'static {}
+
+// SAFETY: Bytes have no padding and any bit pattern is valid. `[u8; N]` already unsizes to `[u8]`.
+unsafe impl Pod for u8 {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            // SAFETY: Primitive integers and floats have no padding and any bit pattern is valid
+            unsafe impl Pod for $ty {}
+
+            // SAFETY: An array of `N` elements spans `size_of::<Self>()` bytes without padding
+            unsafe impl<const N: usize> Unsize<[u8]> for [$ty; N] {
+                unsafe fn target_metadata(self: *const Self) -> <[u8] as Pointee>::Metadata {
+                    mem::size_of::<Self>()
+                }
+
+                unsafe fn target_data_address(self: *const Self) -> *const () {
+                    self.cast()
+                }
+            }
+
+            // SAFETY: The data address is `self`, just like for `Unsize::target_data_address`.
+            // Any bytes written are a valid element as per `Pod`
+            unsafe impl<const N: usize> UnsizeMut<[u8]> for [$ty; N] {
+                unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+                    self.cast()
+                }
+            }
+
+            // SAFETY: The bytes are checked to be aligned and to span a whole number of elements,
+            // any bit pattern is a valid element as per `Pod`
+            unsafe impl TryUnsize<[$ty]> for [u8] {
+                type Error = PodCastError;
+
+                unsafe fn try_target_metadata(
+                    self: *const Self,
+                ) -> Result<<[$ty] as Pointee>::Metadata, PodCastError> {
+                    // SAFETY: self points to a valid instance as per calling contract
+                    check_bytes::<$ty>(unsafe { &*self })
+                }
+
+                unsafe fn target_data_address(self: *const Self) -> *const () {
+                    self.cast()
+                }
+            }
+
+            // SAFETY: The data address is `self`, the elements have no padding so any element
+            // written leaves valid bytes behind
+            unsafe impl TryUnsizeMut<[$ty]> for [u8] {
+                unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+                    self.cast()
+                }
+            }
+        )*
+    };
+}
+
+impl_pod!(i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
+
+// SAFETY: A slice of `len` `T`s spans `len * size_of::<T>()` bytes without padding, as `T: Pod`
+unsafe impl<T: Pod> Unsize<[u8]> for [T] {
+    unsafe fn target_metadata(self: *const Self) -> <[u8] as Pointee>::Metadata {
+        // the size of a live object always fits into an `isize`, so this can't overflow
+        core::ptr::metadata(self) * mem::size_of::<T>()
+    }
+
+    unsafe fn target_data_address(self: *const Self) -> *const () {
+        self.cast()
+    }
+}
+
+// SAFETY: The data address is `self`, just like for `Unsize::target_data_address`. Any bytes
+// written are a valid `T` as per `Pod`
+unsafe impl<T: Pod> UnsizeMut<[u8]> for [T] {
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+        self.cast()
+    }
+}
+
+// SAFETY: The bytes are checked to be aligned and to span a whole number of elements, any bit
+// pattern is a valid `T` as per `Pod`, and `T` has no padding, so any `T` written is valid bytes.
+// Not being layout compatible, this is not `TrySizeOwned`.
+unsafe impl<T: Pod> TrySize<[T]> for [u8] {
+    unsafe fn try_target_metadata(self: *const Self) -> Option<usize> {
+        // SAFETY: self points to a valid instance as per calling contract
+        check_bytes::<T>(unsafe { &*self }).ok()
+    }
+}
+
+/// The error returned when bytes can't be viewed as a slice of some [`Pod`] type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PodCastError {
+    /// The bytes are not aligned for the target type.
+    Misaligned,
+    /// The number of bytes is not a multiple of the size of the target type.
+    SizeMismatch,
+}
+
+impl fmt::Display for PodCastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PodCastError::Misaligned => {
+                f.write_str("the bytes are not aligned for the target type")
+            }
+            PodCastError::SizeMismatch => {
+                f.write_str("the number of bytes is not a multiple of the target type's size")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PodCastError {}
+
+fn check_bytes<T: Pod>(bytes: &[u8]) -> Result<usize, PodCastError> {
+    if bytes.as_ptr().align_offset(mem::align_of::<T>()) != 0 {
+        return Err(PodCastError::Misaligned);
+    }
+    let (len, rest) = (
+        bytes.len() / mem::size_of::<T>(),
+        bytes.len() % mem::size_of::<T>(),
+    );
+    if rest != 0 {
+        return Err(PodCastError::SizeMismatch);
+    }
+    Ok(len)
+}
+
+/// Views a slice of `T`s as its bytes, arrays of any [`Pod`] type coerce to the slice at the call
+/// site.
+pub fn as_bytes<T: Pod>(values: &[T]) -> &[u8] {
+    values.coerce_unsized()
+}
+
+/// Views a mutable slice of `T`s as its bytes, arrays of any [`Pod`] type coerce to the slice at
+/// the call site.
+pub fn as_bytes_mut<T: Pod>(values: &mut [T]) -> &mut [u8] {
+    values.coerce_unsized()
+}
+
+/// Views the bytes as a slice of `T`s, the reverse of the `[T] -> [u8]` unsizing.
+pub fn try_from_bytes<T: Pod>(bytes: &[u8]) -> Result<&[T], PodCastError> {
+    let len = check_bytes::<T>(bytes)?;
+    // SAFETY: The bytes are aligned for `T` and span exactly `len` `T`s, any bit pattern is a
+    // valid `T` as per `Pod`
+    Ok(unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast::<T>(), len) })
+}
+
+/// Views the bytes as a mutable slice of `T`s, the reverse of the `[T] -> [u8]` unsizing.
+pub fn try_from_bytes_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut [T], PodCastError> {
+    let len = check_bytes::<T>(bytes)?;
+    // SAFETY: The bytes are aligned for `T` and span exactly `len` `T`s, any bit pattern is a
+    // valid `T` as per `Pod` and `T` has no padding, so any `T` written is valid bytes
+    Ok(unsafe { core::slice::from_raw_parts_mut(bytes.as_mut_ptr().cast::<T>(), len) })
+}
@@ -0,0 +1,342 @@
+//! This module experiments with a `Pointer` trait that allows disassembling a pointer into its raw
+//! parts and reassembling it from them again. Most non-delegating `CoerceUnsized` impls do exactly
+//! that, so with this trait they collapse into a single function,
+//! [`coerce_pointer`](crate::coerce_unsized::coerce_pointer), that each pointer's impl calls.
+//!
+//! A blanket `CoerceUnsized` impl over all [`Pointer`]s is not possible without negative coherence,
+//! as downstream crates could implement the trait for references to their own types, overlapping
+//! the reference impls. References do implement [`Pointer`], but as they always point to a live
+//! object they keep their own `CoerceUnsized` impls based on the more permissive
+//! [`Unsize`](crate::unsize::Unsize) trait instead of [`FromMetadataUnsize`].
+use core::alloc::Allocator;
+use core::ptr::{self, NonNull, Pointee};
+
+use alloc::boxed::Box;
+use alloc::rc::{self, Rc};
+use alloc::sync::{self, Arc};
+
+/// A pointer type that can be disassembled into a data address, the metadata of its pointee and
+/// some extra state like an allocator, and be reassembled from these parts again.
+///
+/// # Safety
+///
+/// - [`Pointer::into_raw_parts`] must return the address of the pointee and its metadata, passing
+///   the parts unchanged to [`Pointer::from_raw_parts`] must give back an equivalent pointer.
+/// - [`Pointer::from_raw_parts`] must also accept the parts of any pointer it is the
+///   [`Rebind::Output`] of, where the metadata was mapped through
+///   [`FromMetadataUnsize::target_metadata`] to the metadata of `Self::Pointee`, as long as the
+///   old pointee is layout compatible with `Self::Pointee` with that metadata. That is, the pointer
+///   may not store anything that depends on the type of the pointee besides its metadata and
+///   layout. Owning pointers like `Rc` may locate their header from the alignment of the pointee.
+///
+/// [`FromMetadataUnsize::target_metadata`]: crate::unsize::FromMetadataUnsize::target_metadata
+/// [`FromMetadataUnsize`]: crate::unsize::FromMetadataUnsize
+pub unsafe trait Pointer: Sized {
+    /// The type this pointer points to.
+    type Pointee: ?Sized;
+    /// Additional state required for reassembling the pointer, like its allocator.
+    type Extra;
+
+    fn into_raw_parts(self) -> (*const (), <Self::Pointee as Pointee>::Metadata, Self::Extra);
+
+    /// # Safety
+    ///
+    /// The parts must have been obtained from [`Pointer::into_raw_parts`] as described in the
+    /// safety section of [`Pointer`]. If they were obtained from a pointer to another type, that
+    /// type must be layout compatible with `Self::Pointee` with the given metadata, in particular
+    /// it must have the same alignment.
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <Self::Pointee as Pointee>::Metadata,
+        extra: Self::Extra,
+    ) -> Self;
+}
+
+/// A [`Pointer`] that can be rebound to point to a `U` instead.
+///
+/// This is a separate trait instead of a generic associated type on [`Pointer`], as a reference
+/// `&'a T` can only be rebound to `&'a U` if `U: 'a`, a bound a generic associated type can't
+/// express.
+///
+/// # Safety
+///
+/// `Output` must be this pointer type pointing to `U` instead, see the safety section of
+/// [`Pointer`]. Reassembling `Output` from the parts of `Self` requires the pointee of `Self` to
+/// be layout compatible with `U`, in particular to have the same alignment, see
+/// [`Pointer::from_raw_parts`].
+pub unsafe trait Rebind<U: ?Sized>: Pointer {
+    /// This pointer type, but pointing to `U` instead.
+    type Output: Pointer<Pointee = U, Extra = Self::Extra>;
+}
+
+// SAFETY: references are nothing but their address and metadata
+unsafe impl<T: ?Sized> Pointer for &T {
+    type Pointee = T;
+    type Extra = ();
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, ()) {
+        let (data_address, metadata) = (self as *const T).to_raw_parts();
+        (data_address, metadata, ())
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        (): (),
+    ) -> Self {
+        // SAFETY: The parts belong to a live reference as per calling contract
+        unsafe { &*ptr::from_raw_parts(data_address, metadata) }
+    }
+}
+
+// SAFETY: references are nothing but their address and metadata
+unsafe impl<T: ?Sized> Pointer for &mut T {
+    type Pointee = T;
+    type Extra = ();
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, ()) {
+        let (data_address, metadata) = (self as *mut T).to_raw_parts();
+        (data_address.cast_const(), metadata, ())
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        (): (),
+    ) -> Self {
+        // SAFETY: The parts belong to a live unique reference as per calling contract
+        unsafe { &mut *ptr::from_raw_parts_mut(data_address.cast_mut(), metadata) }
+    }
+}
+
+// SAFETY: raw pointers are nothing but their address and metadata
+unsafe impl<T: ?Sized> Pointer for *const T {
+    type Pointee = T;
+    type Extra = ();
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, ()) {
+        let (data_address, metadata) = self.to_raw_parts();
+        (data_address, metadata, ())
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        (): (),
+    ) -> Self {
+        ptr::from_raw_parts(data_address, metadata)
+    }
+}
+
+// SAFETY: raw pointers are nothing but their address and metadata
+unsafe impl<T: ?Sized> Pointer for *mut T {
+    type Pointee = T;
+    type Extra = ();
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, ()) {
+        let (data_address, metadata) = self.to_raw_parts();
+        (data_address.cast_const(), metadata, ())
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        (): (),
+    ) -> Self {
+        ptr::from_raw_parts_mut(data_address.cast_mut(), metadata)
+    }
+}
+
+// SAFETY: `NonNull` is nothing but its address and metadata
+unsafe impl<T: ?Sized> Pointer for NonNull<T> {
+    type Pointee = T;
+    type Extra = ();
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, ()) {
+        let (data_address, metadata) = self.to_raw_parts();
+        (data_address.as_ptr().cast_const(), metadata, ())
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        (): (),
+    ) -> Self {
+        // SAFETY: data_address is derived from a `NonNull` as per calling contract
+        NonNull::from_raw_parts(
+            unsafe { NonNull::new_unchecked(data_address.cast_mut()) },
+            metadata,
+        )
+    }
+}
+
+// SAFETY: The box is reassembled with its original allocation and allocator
+unsafe impl<T: ?Sized, A: Allocator> Pointer for Box<T, A> {
+    type Pointee = T;
+    type Extra = A;
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, A) {
+        let (this, a) = Box::into_raw_with_allocator(self);
+        let (data_address, metadata) = this.to_raw_parts();
+        (data_address.cast_const(), metadata, a)
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        a: A,
+    ) -> Self {
+        // SAFETY: The parts belong to a box allocated in `a` as per calling contract
+        unsafe {
+            Box::from_raw_in(
+                ptr::from_raw_parts_mut(data_address.cast_mut(), metadata),
+                a,
+            )
+        }
+    }
+}
+
+// SAFETY: `Rc::into_raw` returns a pointer to the value, not to the `RcInner`. `Rc::from_raw_in`
+// locates the `RcInner` header from the alignment of the pointee, which is sound as reassembling
+// from the parts of another pointee requires it to be layout compatible. The callers only rebind
+// via `FromMetadataUnsize`, `TryUnsizeOwned` or `TrySizeOwned`, which guarantee that.
+unsafe impl<T: ?Sized, A: Allocator> Pointer for Rc<T, A> {
+    type Pointee = T;
+    type Extra = A;
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, A) {
+        let (this, a) = Rc::into_raw_with_allocator(self);
+        let (data_address, metadata) = this.to_raw_parts();
+        (data_address, metadata, a)
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        a: A,
+    ) -> Self {
+        // SAFETY: The parts belong to an rc allocated in `a` as per calling contract
+        unsafe { Rc::from_raw_in(ptr::from_raw_parts(data_address, metadata), a) }
+    }
+}
+
+// SAFETY: `Arc::into_raw` returns a pointer to the value, not to the `ArcInner`.
+// `Arc::from_raw_in` locates the `ArcInner` header from the alignment of the pointee, which is
+// sound as reassembling from the parts of another pointee requires it to be layout compatible. The
+// callers only rebind via `FromMetadataUnsize`, `TryUnsizeOwned` or `TrySizeOwned`, which
+// guarantee that.
+unsafe impl<T: ?Sized, A: Allocator> Pointer for Arc<T, A> {
+    type Pointee = T;
+    type Extra = A;
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, A) {
+        let (this, a) = Arc::into_raw_with_allocator(self);
+        let (data_address, metadata) = this.to_raw_parts();
+        (data_address, metadata, a)
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        a: A,
+    ) -> Self {
+        // SAFETY: The parts belong to an arc allocated in `a` as per calling contract
+        unsafe { Arc::from_raw_in(ptr::from_raw_parts(data_address, metadata), a) }
+    }
+}
+
+// SAFETY: Same as for `Rc`, `Weak::from_raw_in` locates the `RcInner` header from the alignment
+// of the pointee as well. Additionally it accepts the dangling pointers
+// `Weak::new_in` produces, which are never dereferenced.
+unsafe impl<T: ?Sized, A: Allocator> Pointer for rc::Weak<T, A> {
+    type Pointee = T;
+    type Extra = A;
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, A) {
+        let (this, a) = rc::Weak::into_raw_with_allocator(self);
+        let (data_address, metadata) = this.to_raw_parts();
+        (data_address, metadata, a)
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        a: A,
+    ) -> Self {
+        // SAFETY: The parts belong to a weak allocated in `a` as per calling contract
+        unsafe { rc::Weak::from_raw_in(ptr::from_raw_parts(data_address, metadata), a) }
+    }
+}
+
+// SAFETY: Same as for `Arc`, `Weak::from_raw_in` locates the `ArcInner` header from the alignment
+// of the pointee as well. Additionally it accepts the dangling pointers
+// `Weak::new_in` produces, which are never dereferenced.
+unsafe impl<T: ?Sized, A: Allocator> Pointer for sync::Weak<T, A> {
+    type Pointee = T;
+    type Extra = A;
+
+    fn into_raw_parts(self) -> (*const (), <T as Pointee>::Metadata, A) {
+        let (this, a) = sync::Weak::into_raw_with_allocator(self);
+        let (data_address, metadata) = this.to_raw_parts();
+        (data_address, metadata, a)
+    }
+
+    unsafe fn from_raw_parts(
+        data_address: *const (),
+        metadata: <T as Pointee>::Metadata,
+        a: A,
+    ) -> Self {
+        // SAFETY: The parts belong to a weak allocated in `a` as per calling contract
+        unsafe { sync::Weak::from_raw_in(ptr::from_raw_parts(data_address, metadata), a) }
+    }
+}
+
+// SAFETY: `&'a U` is `&'a T` pointing to `U` instead
+unsafe impl<'a, T: ?Sized, U: ?Sized + 'a> Rebind<U> for &'a T {
+    type Output = &'a U;
+}
+
+// SAFETY: `&'a mut U` is `&'a mut T` pointing to `U` instead
+unsafe impl<'a, T: ?Sized, U: ?Sized + 'a> Rebind<U> for &'a mut T {
+    type Output = &'a mut U;
+}
+
+// SAFETY: `*const U` is `*const T` pointing to `U` instead
+unsafe impl<T: ?Sized, U: ?Sized> Rebind<U> for *const T {
+    type Output = *const U;
+}
+
+// SAFETY: `*mut U` is `*mut T` pointing to `U` instead
+unsafe impl<T: ?Sized, U: ?Sized> Rebind<U> for *mut T {
+    type Output = *mut U;
+}
+
+// SAFETY: `NonNull<U>` is `NonNull<T>` pointing to `U` instead
+unsafe impl<T: ?Sized, U: ?Sized> Rebind<U> for NonNull<T> {
+    type Output = NonNull<U>;
+}
+
+// SAFETY: `Box<U, A>` is `Box<T, A>` pointing to `U` instead
+unsafe impl<T: ?Sized, A: Allocator, U: ?Sized> Rebind<U> for Box<T, A> {
+    type Output = Box<U, A>;
+}
+
+// SAFETY: `Rc<U, A>` is `Rc<T, A>` pointing to `U` instead
+unsafe impl<T: ?Sized, A: Allocator, U: ?Sized> Rebind<U> for Rc<T, A> {
+    type Output = Rc<U, A>;
+}
+
+// SAFETY: `Arc<U, A>` is `Arc<T, A>` pointing to `U` instead
+unsafe impl<T: ?Sized, A: Allocator, U: ?Sized> Rebind<U> for Arc<T, A> {
+    type Output = Arc<U, A>;
+}
+
+// SAFETY: `rc::Weak<U, A>` is `rc::Weak<T, A>` pointing to `U` instead
+unsafe impl<T: ?Sized, A: Allocator, U: ?Sized> Rebind<U> for rc::Weak<T, A> {
+    type Output = rc::Weak<U, A>;
+}
+
+// SAFETY: `sync::Weak<U, A>` is `sync::Weak<T, A>` pointing to `U` instead
+unsafe impl<T: ?Sized, A: Allocator, U: ?Sized> Rebind<U> for sync::Weak<T, A> {
+    type Output = sync::Weak<U, A>;
+}
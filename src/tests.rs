@@ -3,7 +3,8 @@ use core::ptr::addr_of;
 use thin_vec::ThinVec;
 
 use crate::coerce_unsized::CoerceUnsized;
-use crate::unsize::{FromMetadataUnsize, Unsize};
+use crate::pointer::{Pointer, Rebind};
+use crate::unsize::{FromMetadataUnsize, Unsize, UnsizeMut};
 
 use super::*;
 
@@ -30,6 +31,108 @@ fn arc_it() {
     assert_eq!(&*slice, &[0; 10][..]);
 }
 
+#[test]
+fn rc_it() {
+    let concrete = alloc::rc::Rc::new([0; 10]);
+    let weak = alloc::rc::Rc::downgrade(&concrete);
+    let slice: alloc::rc::Rc<[_]> = concrete.coerce_unsized();
+    assert_eq!(&*slice, &[0; 10][..]);
+    let weak: alloc::rc::Weak<[i32]> = weak.coerce_unsized();
+    assert_eq!(&*weak.upgrade().unwrap(), &[0; 10][..]);
+    assert_eq!(alloc::rc::Rc::weak_count(&slice), 1);
+}
+
+#[test]
+fn weak_dangling() {
+    let weak: alloc::rc::Weak<[i32]> = alloc::rc::Weak::<[i32; 10]>::new().coerce_unsized();
+    assert!(weak.upgrade().is_none());
+    let weak: alloc::sync::Weak<[i32]> = alloc::sync::Weak::<[i32; 10]>::new().coerce_unsized();
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn arc_weak() {
+    let concrete = alloc::sync::Arc::new([0; 10]);
+    let weak: alloc::sync::Weak<[i32]> = alloc::sync::Arc::downgrade(&concrete).coerce_unsized();
+    assert_eq!(&*weak.upgrade().unwrap(), &[0; 10][..]);
+    drop(concrete);
+    assert!(weak.upgrade().is_none());
+}
+
+/// Allocator that counts the calls made to it, used to check how often coercions (de)allocate.
+#[derive(Default)]
+struct CountingAllocator {
+    allocations: core::cell::Cell<usize>,
+    deallocations: core::cell::Cell<usize>,
+}
+
+impl CountingAllocator {
+    fn counts(&self) -> (usize, usize) {
+        (self.allocations.get(), self.deallocations.get())
+    }
+}
+
+// SAFETY: All requests are forwarded to the global allocator
+unsafe impl core::alloc::Allocator for &CountingAllocator {
+    fn allocate(
+        &self,
+        layout: core::alloc::Layout,
+    ) -> Result<core::ptr::NonNull<[u8]>, core::alloc::AllocError> {
+        self.allocations.set(self.allocations.get() + 1);
+        alloc::alloc::Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: core::alloc::Layout) {
+        self.deallocations.set(self.deallocations.get() + 1);
+        // SAFETY: ptr was allocated by the global allocator with the given layout
+        unsafe { alloc::alloc::Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn custom_allocator() {
+    let a = CountingAllocator::default();
+
+    let concrete = alloc::boxed::Box::new_in([0; 10], &a);
+    let coerced: alloc::boxed::Box<[i32], _> = concrete.coerce_unsized();
+    assert_eq!(a.counts(), (1, 0));
+    assert_eq!(&*coerced, &[0; 10][..]);
+    drop(coerced);
+    assert_eq!(a.counts(), (1, 1));
+
+    let concrete = alloc::sync::Arc::new_in([0; 10], &a);
+    let weak = alloc::sync::Arc::downgrade(&concrete);
+    let coerced: alloc::sync::Arc<[i32], _> = concrete.coerce_unsized();
+    let weak: alloc::sync::Weak<[i32], _> = weak.coerce_unsized();
+    assert_eq!(a.counts(), (2, 1));
+    assert_eq!(&*weak.upgrade().unwrap(), &[0; 10][..]);
+    drop((coerced, weak));
+    assert_eq!(a.counts(), (2, 2));
+
+    let concrete = alloc::rc::Rc::new_in([0; 10], &a);
+    let weak = alloc::rc::Rc::downgrade(&concrete);
+    let coerced: alloc::rc::Rc<[i32], _> = concrete.coerce_unsized();
+    let weak: alloc::rc::Weak<[i32], _> = weak.coerce_unsized();
+    assert_eq!(a.counts(), (3, 2));
+    assert_eq!(&*weak.upgrade().unwrap(), &[0; 10][..]);
+    drop((coerced, weak));
+    assert_eq!(a.counts(), (3, 3));
+
+    let weak: alloc::rc::Weak<[i32], _> =
+        alloc::rc::Weak::<[i32; 10], _>::new_in(&a).coerce_unsized();
+    assert!(weak.upgrade().is_none());
+    drop(weak);
+    assert_eq!(a.counts(), (3, 3));
+
+    let mut concrete = alloc::vec::Vec::with_capacity_in(10, &a);
+    concrete.extend([0; 10]);
+    let coerced: &[i32] = (&concrete).coerce_unsized();
+    assert_eq!(coerced, &[0; 10][..]);
+    assert_eq!(a.counts(), (4, 3));
+    drop(concrete);
+    assert_eq!(a.counts(), (4, 4));
+}
+
 #[test]
 fn const_unsize_ptr() {
     let coerced: *const [_] = (&[0; 10] as *const [i32; 10]).coerce_unsized();
@@ -37,6 +140,133 @@ fn const_unsize_ptr() {
     assert_eq!(unsafe { &*coerced }, &[0; 10][..]);
 }
 
+#[test]
+fn non_null() {
+    let mut concrete = [0; 10];
+    let coerced: core::ptr::NonNull<[i32]> =
+        core::ptr::NonNull::from(&mut concrete).coerce_unsized();
+    // SAFETY: coerced points to a live slice
+    assert_eq!(unsafe { coerced.as_ref() }, &[0; 10][..]);
+
+    let mut concrete = alloc::vec![0; 3];
+    let coerced: core::ptr::NonNull<[i32]> = (&concrete).coerce_unsized();
+    // SAFETY: coerced points to the elements of a live vec
+    assert_eq!(unsafe { coerced.as_ref() }, &[0; 3][..]);
+    let coerced: core::ptr::NonNull<[i32]> = (&mut concrete).coerce_unsized();
+    // SAFETY: coerced points to the elements of a live vec and was derived from a mutable reference
+    unsafe { (*coerced.as_ptr())[1] = 1 };
+    assert_eq!(concrete, [0, 1, 0]);
+
+    // an intrusive list of trait objects
+    struct Node<T: ?Sized> {
+        next: Option<core::ptr::NonNull<Node<dyn core::fmt::Debug>>>,
+        value: T,
+    }
+    // SAFETY: This would be a compiler provided impl
+    unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> FromMetadataUnsize<Node<U>> for Node<T> {
+        fn target_metadata(
+            metadata: <Self as core::ptr::Pointee>::Metadata,
+        ) -> <Node<U> as core::ptr::Pointee>::Metadata {
+            <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+        }
+    }
+    let mut last = Node {
+        next: None,
+        value: "last",
+    };
+    let first = Node {
+        next: Some(core::ptr::NonNull::from(&mut last)).coerce_unsized(),
+        value: 1,
+    };
+    let next: Option<core::ptr::NonNull<Node<dyn core::fmt::Debug>>> = first.next;
+    // SAFETY: next points to `last` which is still live
+    let next = unsafe { next.unwrap().as_ref() };
+    assert_eq!(alloc::format!("{:?}", &next.value), "\"last\"");
+    assert!(next.next.is_none());
+    let none: Option<core::ptr::NonNull<[i32]>> =
+        None::<core::ptr::NonNull<[i32; 3]>>.coerce_unsized();
+    assert!(none.is_none());
+}
+
+#[test]
+fn custom_pointer() {
+    struct MyBox<T: ?Sized>(alloc::boxed::Box<T>);
+
+    // SAFETY: MyBox is a plain wrapper around a box
+    unsafe impl<T: ?Sized> Pointer for MyBox<T> {
+        type Pointee = T;
+        type Extra = <alloc::boxed::Box<T> as Pointer>::Extra;
+
+        fn into_raw_parts(self) -> (*const (), <T as core::ptr::Pointee>::Metadata, Self::Extra) {
+            self.0.into_raw_parts()
+        }
+
+        unsafe fn from_raw_parts(
+            data_address: *const (),
+            metadata: <T as core::ptr::Pointee>::Metadata,
+            extra: Self::Extra,
+        ) -> Self {
+            // SAFETY: forwarded calling contract
+            MyBox(unsafe { Pointer::from_raw_parts(data_address, metadata, extra) })
+        }
+    }
+
+    // SAFETY: MyBox<U> is MyBox<T> pointing to `U` instead
+    unsafe impl<T: ?Sized, U: ?Sized> Rebind<U> for MyBox<T> {
+        type Output = MyBox<U>;
+    }
+
+    impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> CoerceUnsized<MyBox<U>> for MyBox<T> {
+        fn coerce_unsized(self) -> MyBox<U> {
+            crate::coerce_unsized::coerce_pointer(self)
+        }
+    }
+
+    let concrete = MyBox(alloc::boxed::Box::new([0; 10]));
+    let coerced: MyBox<[i32]> = concrete.coerce_unsized();
+    assert_eq!(&*coerced.0, &[0; 10][..]);
+}
+
+#[test]
+fn pin_coerce() {
+    use alloc::boxed::Box;
+    use core::future::Future;
+    use core::pin::Pin;
+
+    use crate::coerce_unsized::PinCoerceUnsized;
+
+    let mut concrete = [0, 1, 2];
+    let coerced: Pin<&mut [i32]> = Pin::new(&mut concrete).coerce_unsized();
+    assert_eq!(&*coerced, &[0, 1, 2][..]);
+    let coerced: Pin<alloc::rc::Rc<[i32]>> = alloc::rc::Rc::pin([0, 1]).coerce_unsized();
+    assert_eq!(&*coerced, &[0, 1][..]);
+
+    // async blocks are `!Unpin`
+    let concrete = Box::pin(async { 1 });
+    let coerced: Pin<Box<dyn Future<Output = i32>>> = concrete.coerce_unsized();
+    assert_eq!(futures::executor::block_on(coerced), 1);
+    let concrete = alloc::sync::Arc::pin(async { 2 });
+    let coerced: Pin<alloc::sync::Arc<dyn Future<Output = i32> + Send + Sync>> =
+        concrete.coerce_unsized();
+    let _ = coerced;
+
+    #[derive(CoerceUnsized)]
+    struct MyBox<T: ?Sized>(Box<T>);
+    impl<T: ?Sized> core::ops::Deref for MyBox<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+    // SAFETY: MyBox derefs to the pointee of the box, which the coercion of the box keeps in place
+    unsafe impl<T: ?Sized, U: ?Sized> PinCoerceUnsized<MyBox<U>> for MyBox<T> {}
+
+    let concrete = MyBox(Box::new([0; 10]));
+    let coerced: Pin<MyBox<[i32]>> = Pin::new(concrete).coerce_unsized();
+    assert_eq!(&*coerced, &[0; 10][..]);
+}
+
 #[test]
 fn fixed_str() {
     #[repr(transparent)]
@@ -139,25 +369,118 @@ fn fixed_str_dyn_len() {
     assert_eq!(coerced, "foo");
 }
 
-#[test]
-fn thin_vec() {
-    // SAFETY: The metadata returned is valid for the data pointer returned by target_data_address
-    unsafe impl<T> Unsize<[T]> for ThinVec<T> {
-        unsafe fn target_metadata(self: *const Self) -> <[T] as core::ptr::Pointee>::Metadata {
-            // SAFETY: self points a live Self as per calling contract
-            unsafe { (*self).len() }
-        }
+// SAFETY: The metadata returned is valid for the data pointer returned by target_data_address
+unsafe impl<T> Unsize<[T]> for ThinVec<T> {
+    unsafe fn target_metadata(self: *const Self) -> <[T] as core::ptr::Pointee>::Metadata {
+        // SAFETY: self points a live Self as per calling contract
+        unsafe { (*self).len() }
+    }
 
-        unsafe fn target_data_address(self: *const Self) -> *const () {
-            // SAFETY: self points a live Self as per calling contract
-            unsafe { (*self).as_ptr().cast() }
-        }
+    unsafe fn target_data_address(self: *const Self) -> *const () {
+        // SAFETY: self points a live Self as per calling contract
+        unsafe { (*self).as_ptr().cast() }
+    }
+}
+
+// SAFETY: `as_mut_ptr` returns the same address as `as_ptr`, but permits writes
+unsafe impl<T> UnsizeMut<[T]> for ThinVec<T> {
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+        // SAFETY: self points a live Self as per calling contract
+        unsafe { (*self).as_mut_ptr().cast() }
     }
+}
+
+#[test]
+fn thin_vec() {
     let concrete = thin_vec::thin_vec![0; 10];
     let coerced: &[_] = (&concrete).coerce_unsized();
     assert_eq!(coerced, &[0; 10][..]);
 }
 
+#[test]
+fn write_through_mut() {
+    let mut concrete = alloc::vec![0; 3];
+    let coerced: &mut [i32] = (&mut concrete).coerce_unsized();
+    coerced[1] = 1;
+    let coerced: *mut [i32] = (&mut concrete).coerce_unsized();
+    // SAFETY: The pointer is derived from a mutable reference to a live vec
+    unsafe { (*coerced)[2] = 2 };
+    assert_eq!(concrete, [0, 1, 2]);
+
+    let mut concrete = alloc::string::String::from("foo");
+    let coerced: &mut str = (&mut concrete).coerce_unsized();
+    coerced.make_ascii_uppercase();
+    assert_eq!(concrete, "FOO");
+
+    let mut concrete = thin_vec::thin_vec![0; 3];
+    let coerced: &mut [i32] = (&mut concrete).coerce_unsized();
+    coerced.copy_from_slice(&[3, 4, 5]);
+    assert_eq!(concrete, [3, 4, 5][..]);
+
+    #[derive(Unsize)]
+    #[unsize(unsafe(target = [T], data = xs, len = len))]
+    struct ArrayVec<T, const CAP: usize> {
+        len: usize,
+        // the `len` first elements of the array are initialized
+        xs: [core::mem::MaybeUninit<T>; CAP],
+    }
+    let mut concrete = ArrayVec {
+        len: 2,
+        xs: [
+            core::mem::MaybeUninit::new(0),
+            core::mem::MaybeUninit::new(1),
+            core::mem::MaybeUninit::uninit(),
+        ],
+    };
+    let coerced: &mut [i32] = (&mut concrete).coerce_unsized();
+    coerced.reverse();
+    let coerced: &[i32] = (&concrete).coerce_unsized();
+    assert_eq!(coerced, [1, 0]);
+}
+
+#[test]
+fn coerce_unsized_live() {
+    use crate::coerce_unsized::CoerceUnsizedLive;
+
+    #[derive(Unsize)]
+    #[unsize(unsafe(target = [T], data = xs, len = len))]
+    struct ArrayVec<T, const CAP: usize> {
+        len: usize,
+        // the `len` first elements of the array are initialized
+        xs: [core::mem::MaybeUninit<T>; CAP],
+    }
+    let concrete = ArrayVec {
+        len: 1,
+        xs: [
+            core::mem::MaybeUninit::new(0),
+            core::mem::MaybeUninit::uninit(),
+        ],
+    };
+    let ptr: *const ArrayVec<i32, 2> = &concrete;
+    // SAFETY: ptr points to a live ArrayVec
+    let coerced: *const [i32] = unsafe { ptr.coerce_unsized_live() };
+    // SAFETY: The coerced pointer is valid as concrete is still live and unmodified
+    assert_eq!(unsafe { &*coerced }, [0]);
+
+    let mut concrete = alloc::vec![0; 3];
+    let ptr: *mut alloc::vec::Vec<i32> = &mut concrete;
+    // SAFETY: ptr points to a live Vec
+    let coerced: *mut [i32] = unsafe { ptr.coerce_unsized_live() };
+    // SAFETY: The coerced pointer is valid as the vec is still live and did not reallocate
+    unsafe { (*coerced)[1] = 1 };
+    // SAFETY: ptr points to a live Vec
+    let coerced: *const [i32] = unsafe { ptr.coerce_unsized_live() };
+    // SAFETY: The coerced pointer is valid as the vec is still live and did not reallocate
+    assert_eq!(unsafe { &*coerced }, [0, 1, 0]);
+
+    let ptr = core::ptr::NonNull::from(&mut concrete);
+    // SAFETY: ptr points to a live Vec
+    let coerced: core::ptr::NonNull<[i32]> = unsafe { ptr.coerce_unsized_live() };
+    // SAFETY: The coerced pointer is valid as the vec is still live and did not reallocate
+    unsafe { (*coerced.as_ptr())[2] = 2 };
+    assert_eq!(concrete, [0, 1, 2]);
+}
+
 #[test]
 fn to_dyn_trait_coerce() {
     trait Trait {
@@ -168,22 +491,129 @@ fn to_dyn_trait_coerce() {
             alloc::string::ToString::to_string(self)
         }
     }
-    // emulate the compiler impl
-    // SAFETY: i32 and dyn Trait are layout compatible as i32 implements Trait and the metadata produced is a valid vtable for dyn Trait
-    unsafe impl FromMetadataUnsize<dyn Trait> for i32 {
-        fn target_metadata(
-            (): <Self as core::ptr::Pointee>::Metadata,
-        ) -> <dyn Trait as core::ptr::Pointee>::Metadata {
-            core::ptr::metadata::<dyn Trait>(&0 as *const _ as *const _)
+    impl Trait for u8 {
+        fn as_string(&self) -> alloc::string::String {
+            alloc::format!("{self}u8")
         }
     }
+    // emulate the compiler impl
+    crate::impl_dyn_unsize!(dyn Trait);
     let concrete = 0;
     let coerced: &dyn Trait = (&concrete).coerce_unsized();
     assert_eq!(
         coerced.as_string(),
         alloc::string::ToString::to_string(&concrete)
     );
+    let concrete = 1u8;
+    let coerced: &dyn Trait = (&concrete).coerce_unsized();
+    assert_eq!(coerced.as_string(), "1u8");
 }
+
+#[test]
+fn impl_dyn_unsize() {
+    trait Trait<X> {
+        fn get(&self) -> X;
+    }
+    impl<X: Copy> Trait<X> for (X, u8) {
+        fn get(&self) -> X {
+            self.0
+        }
+    }
+    crate::impl_dyn_unsize!(<X> dyn Trait<X> + Send + Sync);
+    trait Name {
+        fn name(&self) -> &'static str;
+    }
+    impl Name for alloc::string::String {
+        fn name(&self) -> &'static str {
+            "String"
+        }
+    }
+    impl Name for &'static str {
+        fn name(&self) -> &'static str {
+            "&str"
+        }
+    }
+    crate::impl_dyn_unsize!(alloc::string::String, &'static str => dyn Name + Send);
+
+    let concrete = (5i32, 1);
+    let coerced: alloc::boxed::Box<dyn Trait<i32> + Send + Sync> =
+        alloc::boxed::Box::new(concrete).coerce_unsized();
+    assert_eq!(coerced.get(), 5);
+    let concrete = ('a', 1);
+    let coerced: &(dyn Trait<char> + Send + Sync) = (&concrete).coerce_unsized();
+    assert_eq!(coerced.get(), 'a');
+
+    let concrete = alloc::string::String::from("foo");
+    let coerced: &(dyn Name + Send) = (&concrete).coerce_unsized();
+    assert_eq!(coerced.name(), "String");
+    let concrete = "bar";
+    let coerced: &(dyn Name + Send) = (&concrete).coerce_unsized();
+    assert_eq!(coerced.name(), "&str");
+}
+#[test]
+fn builtin_dyn_unsize() {
+    use alloc::boxed::Box;
+    use alloc::string::{String, ToString};
+    use core::any::Any;
+    use core::fmt::{Debug, Display};
+    use core::future::Future;
+
+    let concrete = alloc::vec![1, 2];
+    let coerced: &dyn Debug = (&concrete).coerce_unsized();
+    assert_eq!(alloc::format!("{coerced:?}"), "[1, 2]");
+    let concrete = Some("foo");
+    let coerced: &(dyn Debug + Send + Sync) = (&concrete).coerce_unsized();
+    assert_eq!(alloc::format!("{coerced:?}"), "Some(\"foo\")");
+
+    let concrete = 'x';
+    let coerced: &(dyn Display + Send) = (&concrete).coerce_unsized();
+    assert_eq!(coerced.to_string(), "x");
+    let concrete = String::from("foo");
+    let coerced: Box<dyn Display + Sync> = Box::new(concrete).coerce_unsized();
+    assert_eq!(coerced.to_string(), "foo");
+
+    let concrete = 3u8;
+    let coerced: &dyn Any = (&concrete).coerce_unsized();
+    assert_eq!(coerced.downcast_ref::<u8>(), Some(&3));
+    assert!(!coerced.is::<i8>());
+    let concrete = String::from("foo");
+    let coerced: Box<dyn Any + Send> = Box::new(concrete).coerce_unsized();
+    assert_eq!(*coerced.downcast::<String>().unwrap(), "foo");
+
+    let concrete = core::fmt::Error;
+    let coerced: &(dyn core::error::Error + Send + Sync) = (&concrete).coerce_unsized();
+    assert_eq!(coerced.to_string(), concrete.to_string());
+    assert!(coerced.source().is_none());
+
+    let offset = 2;
+    let concrete = move |x: i32| x + offset;
+    let coerced: &dyn Fn(i32) -> i32 = (&concrete).coerce_unsized();
+    assert_eq!(coerced(1), 3);
+    let mut count = 0;
+    let mut concrete = || count += 1;
+    let coerced: &mut (dyn FnMut() + Send) = (&mut concrete).coerce_unsized();
+    coerced();
+    coerced();
+    assert_eq!(count, 2);
+    let name = String::from("foo");
+    let concrete = move |suffix: String| name + &suffix;
+    let coerced: Box<dyn FnOnce(String) -> String + Send + Sync> =
+        Box::new(concrete).coerce_unsized();
+    assert_eq!(coerced(String::from("bar")), "foobar");
+
+    let mut concrete = 0..4;
+    let coerced: &mut dyn Iterator<Item = i32> = (&mut concrete).coerce_unsized();
+    assert_eq!(coerced.next(), Some(0));
+    assert_eq!(coerced.sum::<i32>(), 6);
+    let concrete = alloc::vec![String::from("a"), String::from("b")].into_iter();
+    let coerced: Box<dyn Iterator<Item = String> + Send> = Box::new(concrete).coerce_unsized();
+    assert_eq!(coerced.collect::<String>(), "ab");
+
+    let concrete = async { 1 };
+    let coerced: Box<dyn Future<Output = i32> + Send> = Box::new(concrete).coerce_unsized();
+    assert_eq!(futures::executor::block_on(Box::into_pin(coerced)), 1);
+}
+
 #[test]
 fn to_dyn_trait_coerce_upcast() {
     trait Super {
@@ -196,25 +626,16 @@ fn to_dyn_trait_coerce_upcast() {
         }
     }
     impl Trait for i32 {}
-    // emulate the compiler impl
-    // SAFETY: i32 and dyn Trait are layout compatible as i32 implements Trait and the metadata produced is a valid vtable for dyn Trait
-    unsafe impl FromMetadataUnsize<dyn Trait> for i32 {
-        fn target_metadata(
-            (): <Self as core::ptr::Pointee>::Metadata,
-        ) -> <dyn Trait as core::ptr::Pointee>::Metadata {
-            core::ptr::metadata::<dyn Trait>(&0 as *const _ as *const _)
-        }
-    }
-    // emulate the compiler impl
-    // SAFETY:
-    unsafe impl FromMetadataUnsize<dyn Super> for dyn Trait {
-        fn target_metadata(
-            _: <Self as core::ptr::Pointee>::Metadata,
-        ) -> <dyn Super as core::ptr::Pointee>::Metadata {
-            // This isn't really correct obviously, but there is no proper to emulate what the compiler does here
-            core::ptr::metadata(&0i32 as &dyn Super)
+    impl Super for u8 {
+        fn as_super_string(&self) -> alloc::string::String {
+            alloc::format!("{self}u8")
         }
     }
+    impl Trait for u8 {}
+    // emulate the compiler impls
+    crate::impl_dyn_unsize!(dyn Trait);
+    crate::impl_upcast!(dyn Trait => dyn Super);
+
     let concrete = 0;
     // ref
     let coerced: &dyn Trait = (&concrete).coerce_unsized();
@@ -231,6 +652,48 @@ fn to_dyn_trait_coerce_upcast() {
         unsafe { (*coerced).as_super_string() },
         alloc::string::ToString::to_string(&concrete)
     );
+    // the vtable belongs to the actual type
+    let concrete = 1u8;
+    let coerced: alloc::boxed::Box<dyn Trait> = alloc::boxed::Box::new(concrete).coerce_unsized();
+    let coerced: alloc::boxed::Box<dyn Super> = coerced.coerce_unsized();
+    assert_eq!(coerced.as_super_string(), "1u8");
+}
+
+#[test]
+fn upcast_chain() {
+    trait Base {
+        fn base(&self) -> u32;
+    }
+    trait Middle: Base {
+        fn middle(&self) -> u32;
+    }
+    trait Top: Middle {}
+    struct Foo(u32);
+    impl Base for Foo {
+        fn base(&self) -> u32 {
+            self.0
+        }
+    }
+    impl Middle for Foo {
+        fn middle(&self) -> u32 {
+            self.0 * 2
+        }
+    }
+    impl Top for Foo {}
+    crate::impl_dyn_unsize!(dyn Top + Send);
+    crate::impl_upcast!(dyn Top + Send => dyn Middle + Send => dyn Base + Send);
+    crate::impl_upcast!(dyn Middle + Send => dyn Base);
+
+    let concrete = Foo(3);
+    let top: &(dyn Top + Send) = (&concrete).coerce_unsized();
+    let middle: &(dyn Middle + Send) = top.coerce_unsized();
+    assert_eq!(middle.middle(), 6);
+    let base: &(dyn Base + Send) = middle.coerce_unsized();
+    assert_eq!(base.base(), 3);
+    let base: &(dyn Base + Send) = top.coerce_unsized();
+    assert_eq!(base.base(), 3);
+    let base: &dyn Base = middle.coerce_unsized();
+    assert_eq!(base.base(), 3);
 }
 
 #[test]
@@ -277,6 +740,78 @@ fn compiler_adt_builtin_coerce() {
     assert_eq!(&coerced.field.field, &[0; 10][..]);
 }
 
+#[test]
+fn derive_unsize() {
+    #[derive(Unsize)]
+    struct Foo<T: ?Sized> {
+        len: usize,
+        field: T,
+    }
+    #[derive(Unsize)]
+    struct Bar<'a, X, #[unsize] T: ?Sized> {
+        other: &'a X,
+        field: T,
+    }
+
+    let concrete = Foo {
+        len: 10,
+        field: Bar {
+            other: &0u8,
+            field: [0; 10],
+        },
+    };
+    let coerced: &Foo<Bar<u8, [i32]>> = (&concrete).coerce_unsized();
+    assert_eq!((coerced.len, coerced.field.other), (10, &0));
+    assert_eq!(&coerced.field.field, &[0; 10][..]);
+
+    let concrete = alloc::boxed::Box::new(Foo {
+        len: 10,
+        field: [0; 10],
+    });
+    let coerced: alloc::boxed::Box<Foo<[i32]>> = concrete.coerce_unsized();
+    assert_eq!(&coerced.field, &[0; 10][..]);
+}
+
+#[test]
+fn derive_unsize_projection() {
+    #[derive(Unsize)]
+    #[unsize(unsafe(target = [T], data = xs, len = len))]
+    struct ArrayVec<T, const CAP: usize> {
+        len: usize,
+        // the `len` first elements of the array are initialized
+        xs: [core::mem::MaybeUninit<T>; CAP],
+    }
+    #[derive(Unsize)]
+    #[unsize(unsafe(target = str, data = 1, len = 0))]
+    struct FixedStringWithLen<const N: usize>(usize, [u8; N]);
+
+    let concrete = ArrayVec {
+        len: 2,
+        xs: [
+            core::mem::MaybeUninit::new(0i32),
+            core::mem::MaybeUninit::new(1),
+            core::mem::MaybeUninit::uninit(),
+        ],
+    };
+    let coerced: &[i32] = (&concrete).coerce_unsized();
+    assert_eq!(coerced, &[0, 1]);
+
+    let concrete = FixedStringWithLen(3, *b"foo\0\0\0\0\0");
+    let coerced: &str = (&concrete).coerce_unsized();
+    assert_eq!(coerced, "foo");
+}
+
+#[test]
+#[should_panic = "`len` exceeds the capacity of `data`"]
+fn derive_unsize_projection_out_of_bounds() {
+    #[derive(Unsize)]
+    #[unsize(unsafe(target = str, data = 1, len = 0))]
+    struct FixedStringWithLen<const N: usize>(usize, [u8; N]);
+
+    let concrete = FixedStringWithLen(4, *b"foo");
+    let _: &str = (&concrete).coerce_unsized();
+}
+
 #[test]
 fn coerce_type_metadata() {
     struct Struct;
@@ -329,6 +864,195 @@ fn option_coerce() {
     assert_eq!(coerced, Option::None);
 }
 
+#[test]
+fn wrapper_coerce() {
+    use alloc::boxed::Box;
+    use core::cell::{OnceCell, RefCell, UnsafeCell};
+    use core::marker::PhantomData;
+    use core::mem::ManuallyDrop;
+
+    let concrete = RefCell::new(Box::new([0; 2]));
+    let coerced: RefCell<Box<[i32]>> = concrete.coerce_unsized();
+    coerced.borrow_mut()[1] = 1;
+    assert_eq!(*coerced.into_inner(), [0, 1]);
+
+    let concrete = UnsafeCell::new(Box::new([0; 2]));
+    let coerced: UnsafeCell<Box<[i32]>> = concrete.coerce_unsized();
+    assert_eq!(*coerced.into_inner(), [0, 0]);
+
+    let concrete = OnceCell::new();
+    concrete.set(alloc::sync::Arc::new([0; 2])).unwrap();
+    let coerced: OnceCell<alloc::sync::Arc<[i32]>> = concrete.coerce_unsized();
+    assert_eq!(*coerced.get().unwrap().as_ref(), [0, 0]);
+    let concrete = OnceCell::<alloc::sync::Arc<[i32; 2]>>::new();
+    let coerced: OnceCell<alloc::sync::Arc<[i32]>> = concrete.coerce_unsized();
+    assert!(coerced.get().is_none());
+
+    let concrete = ManuallyDrop::new(Box::new([0; 2]));
+    let coerced: ManuallyDrop<Box<[i32]>> = concrete.coerce_unsized();
+    assert_eq!(**coerced, [0, 0]);
+    drop(ManuallyDrop::into_inner(coerced));
+
+    let concrete = PhantomData::<[i32; 2]>;
+    let _: PhantomData<[i32]> = concrete.coerce_unsized();
+    let concrete = PhantomData::<u8>;
+    let _: PhantomData<dyn core::fmt::Debug> = concrete.coerce_unsized();
+    let concrete = PhantomData::<fn() -> [i32; 2]>;
+    let _: PhantomData<fn() -> [i32]> = concrete.coerce_unsized();
+    let concrete = PhantomData::<fn() -> u8>;
+    let _: PhantomData<fn() -> dyn core::fmt::Debug> = concrete.coerce_unsized();
+}
+
+#[test]
+fn wrapper_struct_tail_unsize() {
+    use alloc::boxed::Box;
+    use alloc::rc::Rc;
+    use core::cell::{Cell, RefCell, UnsafeCell};
+    use core::mem::ManuallyDrop;
+
+    let concrete = Rc::new(RefCell::new([0; 3]));
+    let coerced: Rc<RefCell<[i32]>> = concrete.clone().coerce_unsized();
+    coerced.borrow_mut()[1] = 1;
+    assert_eq!(*concrete.borrow(), [0, 1, 0]);
+    let coerced: Box<RefCell<dyn core::fmt::Debug>> = Box::new(RefCell::new(1u64)).coerce_unsized();
+    assert_eq!(alloc::format!("{:?}", &*coerced.borrow()), "1");
+
+    let concrete = Cell::new([0; 3]);
+    let coerced: &Cell<[i32]> = (&concrete).coerce_unsized();
+    coerced.as_slice_of_cells()[2].set(2);
+    assert_eq!(concrete.get(), [0, 0, 2]);
+
+    let concrete = UnsafeCell::new([0; 3]);
+    let coerced: &UnsafeCell<[i32]> = (&concrete).coerce_unsized();
+    assert_eq!(core::ptr::metadata(coerced.get()), 3);
+
+    let concrete: Box<ManuallyDrop<[alloc::string::String; 1]>> =
+        Box::new(ManuallyDrop::new([alloc::string::String::from("foo")]));
+    let mut coerced: Box<ManuallyDrop<[alloc::string::String]>> = concrete.coerce_unsized();
+    assert_eq!(coerced[..], ["foo"]);
+    // SAFETY: The strings are not used afterwards
+    unsafe { ManuallyDrop::drop(&mut coerced) };
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn std_struct_tail_unsize() {
+    use alloc::sync::Arc;
+    use std::sync::{Mutex, RwLock};
+
+    let concrete = Arc::new(Mutex::new([0; 3]));
+    let coerced: Arc<Mutex<[i32]>> = concrete.clone().coerce_unsized();
+    coerced.lock().unwrap()[1] = 1;
+    assert_eq!(*concrete.lock().unwrap(), [0, 1, 0]);
+
+    let concrete = Arc::new(RwLock::new(2u8));
+    let coerced: Arc<RwLock<dyn core::fmt::Display + Send + Sync>> = concrete.coerce_unsized();
+    assert_eq!(
+        std::string::ToString::to_string(&*coerced.read().unwrap()),
+        "2"
+    );
+}
+
+#[test]
+fn tuple_tail_unsize() {
+    let concrete = (1u8, [0; 4]);
+    let coerced: &(u8, [i32]) = (&concrete).coerce_unsized();
+    assert_eq!((coerced.0, &coerced.1), (1, &[0; 4][..]));
+
+    trait Trait {
+        fn get(&self) -> i32;
+    }
+    impl Trait for i32 {
+        fn get(&self) -> i32 {
+            *self
+        }
+    }
+    crate::impl_dyn_unsize!(dyn Trait);
+    let concrete = alloc::boxed::Box::new((alloc::string::String::from("foo"), 2));
+    let coerced: alloc::boxed::Box<(alloc::string::String, dyn Trait)> = concrete.coerce_unsized();
+    assert_eq!((&*coerced.0, coerced.1.get()), ("foo", 2));
+
+    type Twelve<T> = (u8, u16, u32, u64, u8, u16, u32, u64, u8, u16, u32, T);
+    let concrete: Twelve<[i32; 2]> = (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, [12; 2]);
+    let coerced: &Twelve<[i32]> = (&concrete).coerce_unsized();
+    assert_eq!((coerced.10, &coerced.11), (11, &[12; 2][..]));
+}
+
+#[test]
+fn core_option_result_tuple_coerce() {
+    fn first_chunk(xs: &[u8]) -> Option<&[u8; 2]> {
+        xs.get(..2)?.try_into().ok()
+    }
+    let coerced: Option<&[u8]> = first_chunk(&[0, 1, 2]).coerce_unsized();
+    assert_eq!(coerced, Some(&[0, 1][..]));
+    let coerced: Option<&[u8]> = first_chunk(&[0]).coerce_unsized();
+    assert_eq!(coerced, None);
+    let concrete = Some(core::ptr::NonNull::from(&[0u8; 3]));
+    let coerced: Option<core::ptr::NonNull<[u8]>> = concrete.coerce_unsized();
+    assert_eq!(core::ptr::metadata(coerced.unwrap().as_ptr()), 3);
+
+    let concrete: Result<alloc::boxed::Box<[i32; 2]>, &str> = Ok(alloc::boxed::Box::new([0, 1]));
+    let coerced: Result<alloc::boxed::Box<[i32]>, &str> = concrete.coerce_unsized();
+    assert_eq!(coerced.as_deref(), Ok(&[0, 1][..]));
+    let concrete: Result<alloc::boxed::Box<[i32; 2]>, &str> = Err("error");
+    let coerced: Result<alloc::boxed::Box<[i32]>, &str> = concrete.coerce_unsized();
+    assert_eq!(coerced, Err("error"));
+
+    let concrete = (&[0; 1], alloc::rc::Rc::new(alloc::vec![1]));
+    let coerced: (&[i32], &dyn core::fmt::Debug) = (concrete.0, &concrete.1).coerce_unsized();
+    assert_eq!(coerced.0, [0]);
+    assert_eq!(alloc::format!("{:?}", coerced.1), "[1]");
+    let concrete = [&[0; 2], &[1; 2]];
+    let coerced: [&[i32]; 2] = concrete.coerce_unsized();
+    assert_eq!(coerced, [&[0, 0][..], &[1, 1][..]]);
+}
+
+#[test]
+fn derive_coerce_unsized() {
+    #[derive(CoerceUnsized)]
+    struct Wrapper<'a, #[coerce_unsized] T: ?Sized, X> {
+        ptr: &'a T,
+        extra: X,
+        marker: core::marker::PhantomData<fn() -> alloc::boxed::Box<T>>,
+    }
+
+    #[derive(CoerceUnsized)]
+    struct Tuple<T: ?Sized>(usize, alloc::boxed::Box<T>);
+
+    #[derive(CoerceUnsized)]
+    enum Either<T: ?Sized> {
+        Left(alloc::boxed::Box<T>),
+        Right { rc: alloc::rc::Rc<T>, tag: u8 },
+        Neither,
+    }
+
+    let concrete = Wrapper {
+        ptr: &[0; 10],
+        extra: 1u8,
+        marker: core::marker::PhantomData,
+    };
+    let coerced: Wrapper<[i32], _> = concrete.coerce_unsized();
+    assert_eq!(coerced.ptr, &[0; 10][..]);
+    assert_eq!(coerced.extra, 1);
+
+    let concrete = Tuple(1, alloc::boxed::Box::new([0; 10]));
+    let coerced: Tuple<[i32]> = concrete.coerce_unsized();
+    assert_eq!((coerced.0, &*coerced.1), (1, &[0; 10][..]));
+
+    let concrete = Either::Left(alloc::boxed::Box::new([0; 10]));
+    let coerced: Either<[i32]> = concrete.coerce_unsized();
+    assert!(matches!(coerced, Either::Left(b) if *b == [0; 10]));
+    let concrete = Either::Right {
+        rc: alloc::rc::Rc::new([0; 10]),
+        tag: 1,
+    };
+    let coerced: Either<[i32]> = concrete.coerce_unsized();
+    assert!(matches!(coerced, Either::Right { rc, tag: 1 } if *rc == [0; 10]));
+    let concrete = Either::<[i32; 10]>::Neither;
+    let coerced: Either<[i32]> = concrete.coerce_unsized();
+    assert!(matches!(coerced, Either::Neither));
+}
+
 // https://github.com/rust-lang/rfcs/pull/1792
 #[test]
 fn coerce_ref_option_inner() {
@@ -360,9 +1084,457 @@ fn coerce_ref_option_inner() {
     assert_eq!(coerced, Option::None);
 }
 
+#[test]
+fn coerce_ref_core_option_result_inner() {
+    let concrete = Some(alloc::vec![0, 1, 2]);
+    let coerced: Option<&[i32]> = (&concrete).coerce_unsized();
+    assert_eq!(coerced, Some(&[0, 1, 2][..]));
+    let concrete = None::<alloc::vec::Vec<i32>>;
+    let coerced: Option<&[i32]> = (&concrete).coerce_unsized();
+    assert_eq!(coerced, None);
+
+    // the plain reference impls still apply
+    let coerced: &dyn core::fmt::Debug = (&concrete).coerce_unsized();
+    assert_eq!(alloc::format!("{coerced:?}"), "None");
+
+    let mut concrete = Some(alloc::vec![0, 1, 2]);
+    let coerced: Option<&mut [i32]> = (&mut concrete).coerce_unsized();
+    coerced.unwrap()[0] = 3;
+    assert_eq!(concrete, Some(alloc::vec![3, 1, 2]));
+
+    let concrete: Result<_, u8> = Ok(alloc::string::String::from("foo"));
+    let coerced: Result<&str, &u8> = (&concrete).coerce_unsized();
+    assert_eq!(coerced, Ok("foo"));
+    let concrete: Result<alloc::string::String, u8> = Err(1);
+    let coerced: Result<&str, &u8> = (&concrete).coerce_unsized();
+    assert_eq!(coerced, Err(&1));
+
+    let mut concrete: Result<_, u8> = Ok([0; 2]);
+    let coerced: Result<&mut dyn core::fmt::Debug, &mut u8> = (&mut concrete).coerce_unsized();
+    assert_eq!(alloc::format!("{:?}", coerced.unwrap()), "[0, 0]");
+    let mut concrete: Result<[i32; 2], u8> = Err(1);
+    let coerced: Result<&mut [i32], &mut u8> = (&mut concrete).coerce_unsized();
+    *coerced.unwrap_err() = 2;
+    assert_eq!(concrete, Err(2));
+}
+
+#[test]
+fn flatten_nested_arrays() {
+    let concrete = [[0, 1], [2, 3], [4, 5]];
+    let nested: &[[i32; 2]] = &concrete;
+    let coerced: &[i32] = nested.coerce_unsized();
+    assert_eq!(coerced, &[0, 1, 2, 3, 4, 5]);
+    let coerced: &[i32] = (&concrete).coerce_unsized();
+    assert_eq!(coerced, &[0, 1, 2, 3, 4, 5]);
+
+    let mut concrete = [[0u8; 3]; 2];
+    let coerced: &mut [u8] = (&mut concrete).coerce_unsized();
+    coerced[4] = 1;
+    assert_eq!(concrete, [[0, 0, 0], [0, 1, 0]]);
+
+    let nested: alloc::boxed::Box<[[i32; 2]]> = alloc::vec![[0, 1], [2, 3]].into_boxed_slice();
+    let coerced: alloc::boxed::Box<[i32]> = nested.coerce_unsized();
+    assert_eq!(&*coerced, &[0, 1, 2, 3]);
+    let coerced: alloc::boxed::Box<[i32]> = alloc::boxed::Box::new([[0; 2]; 3]).coerce_unsized();
+    assert_eq!(coerced.len(), 6);
+
+    let nested: alloc::sync::Arc<[[u16; 2]]> = alloc::sync::Arc::from([[0, 1], [2, 3]]);
+    let coerced: alloc::sync::Arc<[u16]> = nested.coerce_unsized();
+    assert_eq!(&*coerced, &[0, 1, 2, 3]);
+}
+
+#[test]
+#[should_panic = "the flattened slice length overflows `usize`"]
+fn flatten_nested_arrays_overflow() {
+    let concrete = [[(); usize::MAX]; 2];
+    let nested: &[[(); usize::MAX]] = &concrete;
+    let _: &[()] = nested.coerce_unsized();
+}
+
+#[test]
+fn pod_byte_views() {
+    let concrete = [0x0102_0304u32, 0x0506_0708];
+    let bytes: &[u8] = (&concrete).coerce_unsized();
+    assert_eq!(bytes.len(), 8);
+    assert_eq!(bytes[..4], 0x0102_0304u32.to_ne_bytes());
+
+    let mut concrete = alloc::vec![0u16; 3];
+    let slice: &mut [u16] = &mut concrete;
+    let bytes: &mut [u8] = slice.coerce_unsized();
+    bytes[2..4].copy_from_slice(&7u16.to_ne_bytes());
+    assert_eq!(concrete, [0, 7, 0]);
+
+    let ptr: *const [u8] = (&[1.0f64; 2][..]).coerce_unsized();
+    assert_eq!(core::ptr::metadata(ptr), 16);
+
+    let concrete = [1u32, 2, 3];
+    let bytes: &[u8] = (&concrete).coerce_unsized();
+    assert_eq!(crate::pod::try_from_bytes::<u32>(bytes), Ok(&concrete[..]));
+    assert_eq!(
+        crate::pod::try_from_bytes::<u32>(&bytes[..6]),
+        Err(crate::pod::PodCastError::SizeMismatch)
+    );
+    assert_eq!(
+        crate::pod::try_from_bytes::<u32>(&bytes[1..5]),
+        Err(crate::pod::PodCastError::Misaligned)
+    );
+
+    let mut concrete = [0u64; 2];
+    let bytes: &mut [u8] = (&mut concrete).coerce_unsized();
+    crate::pod::try_from_bytes_mut::<u32>(bytes).unwrap()[3] = u32::MAX;
+    let bytes: &[u8] = (&concrete).coerce_unsized();
+    assert_eq!(bytes[8..], [0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Vertex {
+        x: f32,
+        y: f32,
+    }
+    // SAFETY: Two `f32`s without padding, any bit pattern is a valid vertex
+    unsafe impl crate::pod::Pod for Vertex {}
+
+    let mut vertices = [Vertex { x: 1.0, y: 2.0 }; 3];
+    let bytes = crate::pod::as_bytes(&vertices);
+    assert_eq!(bytes.len(), 24);
+    assert_eq!(bytes[4..8], 2.0f32.to_ne_bytes());
+    let slice: &[Vertex] = (&vertices).coerce_unsized();
+    let bytes: &[u8] = slice.coerce_unsized();
+    assert_eq!(
+        crate::pod::try_from_bytes::<Vertex>(bytes),
+        Ok(&vertices[..])
+    );
+    crate::pod::as_bytes_mut(&mut vertices)[16..20].copy_from_slice(&3.0f32.to_ne_bytes());
+    assert_eq!(vertices[2], Vertex { x: 3.0, y: 2.0 });
+}
+
+#[test]
+fn transposing_unsize() {
+    let mut buffer = alloc::boxed::Box::new(core::mem::MaybeUninit::<[u32; 4]>::uninit());
+    let elements: &mut [core::mem::MaybeUninit<u32>] = (&mut *buffer).coerce_unsized();
+    for (i, element) in elements.iter_mut().enumerate() {
+        element.write(i as u32);
+    }
+    // SAFETY: all elements have been initialized above
+    let buffer = unsafe { buffer.assume_init() };
+    assert_eq!(*buffer, [0, 1, 2, 3]);
+
+    let buffer: alloc::boxed::Box<core::mem::MaybeUninit<[u8; 3]>> =
+        alloc::boxed::Box::new(core::mem::MaybeUninit::new([1, 2, 3]));
+    let elements: alloc::boxed::Box<[core::mem::MaybeUninit<u8>]> = buffer.coerce_unsized();
+    assert_eq!(elements.len(), 3);
+
+    let concrete = core::cell::Cell::new([0, 1, 2]);
+    let cells: &[core::cell::Cell<i32>] = (&concrete).coerce_unsized();
+    cells[1].set(5);
+    cells[0].swap(&cells[1]);
+    assert_eq!(concrete.get(), [5, 0, 2]);
+
+    let concrete = core::cell::UnsafeCell::new([0u8; 2]);
+    let cells: &[core::cell::UnsafeCell<u8>] = (&concrete).coerce_unsized();
+    // SAFETY: there are no other accesses to the cell's contents
+    unsafe { *cells[1].get() = 1 };
+    assert_eq!(concrete.into_inner(), [0, 1]);
+}
+
+#[test]
+fn str_bytes() {
+    let bytes: &[u8] = "foo".coerce_unsized();
+    assert_eq!(bytes, b"foo");
+    let mut concrete = alloc::string::String::from("bar");
+    let bytes: &[u8] = concrete.as_mut_str().coerce_unsized();
+    assert_eq!(bytes, b"bar");
+
+    let boxed: alloc::boxed::Box<str> = "foo".into();
+    let bytes: alloc::boxed::Box<[u8]> = boxed.coerce_unsized();
+    assert_eq!(&*bytes, b"foo");
+    let arced: alloc::sync::Arc<str> = "foo".into();
+    let bytes: alloc::sync::Arc<[u8]> = arced.coerce_unsized();
+    assert_eq!(&*bytes, b"foo");
+    let rced: alloc::rc::Rc<str> = "foo".into();
+    let bytes: alloc::rc::Rc<[u8]> = rced.coerce_unsized();
+
+    let s = crate::coerce_unsized::str_from_utf8(bytes).unwrap();
+    assert_eq!(&*s, "foo");
+    let bytes: alloc::boxed::Box<[u8]> = alloc::boxed::Box::new(*b"bar").coerce_unsized();
+    let s = crate::coerce_unsized::str_from_utf8(bytes).unwrap();
+    assert_eq!(&*s, "bar");
+    let bytes: alloc::sync::Arc<[u8]> = alloc::sync::Arc::new([0xff, 0]).coerce_unsized();
+    let (bytes, error) = crate::coerce_unsized::str_from_utf8(bytes).unwrap_err();
+    assert_eq!((&*bytes, error.valid_up_to()), (&[0xff, 0][..], 0));
+
+    let a = CountingAllocator::default();
+    let bytes: alloc::boxed::Box<[u8], _> = alloc::boxed::Box::new_in(*b"baz", &a).coerce_unsized();
+    let s = crate::coerce_unsized::str_from_utf8(bytes).unwrap();
+    let bytes: alloc::boxed::Box<[u8], _> = s.coerce_unsized();
+    assert_eq!(&*bytes, b"baz");
+    drop(bytes);
+    assert_eq!(a.counts(), (1, 1));
+}
+
+#[test]
+fn try_coerce_unsized() {
+    use crate::coerce_unsized::TryCoerceUnsized;
+
+    let bytes: &[u8] = b"foo";
+    let s: &str = bytes.try_coerce_unsized().unwrap();
+    assert_eq!(s, "foo");
+    let bytes: &[u8] = &[0xff];
+    let (bytes, error): (_, core::str::Utf8Error) =
+        TryCoerceUnsized::<&str>::try_coerce_unsized(bytes).unwrap_err();
+    assert_eq!((bytes, error.valid_up_to()), (&[0xff][..], 0));
+
+    let mut concrete = *b"bar";
+    let s: &mut str = (&mut concrete).try_coerce_unsized().unwrap();
+    s.make_ascii_uppercase();
+    assert_eq!(&concrete, b"BAR");
+
+    let boxed: alloc::boxed::Box<[u8; 3]> = alloc::boxed::Box::new(*b"foo");
+    let s: alloc::boxed::Box<str> = boxed.try_coerce_unsized().unwrap();
+    assert_eq!(&*s, "foo");
+    let rced: alloc::rc::Rc<[u8]> = alloc::rc::Rc::from(&b"foo"[..]);
+    let s: alloc::rc::Rc<str> = rced.try_coerce_unsized().unwrap();
+    assert_eq!(&*s, "foo");
+    let arced: alloc::sync::Arc<[u8]> = alloc::sync::Arc::from(&[0xc0][..]);
+    let (arced, _) =
+        TryCoerceUnsized::<alloc::sync::Arc<str>>::try_coerce_unsized(arced).unwrap_err();
+    assert_eq!(&*arced, &[0xc0]);
+
+    // infallible coercions are fallible coercions as well
+    let slice: &[i32] = (&[1, 2, 3]).try_coerce_unsized().unwrap();
+    assert_eq!(slice, &[1, 2, 3]);
+    let slice: alloc::boxed::Box<[i32]> = alloc::boxed::Box::new([1, 2, 3])
+        .try_coerce_unsized()
+        .unwrap();
+    assert_eq!(&*slice, &[1, 2, 3]);
+
+    let concrete = [1u32, 2];
+    let bytes: &[u8] = (&concrete).coerce_unsized();
+    let slice: &[u32] = bytes.try_coerce_unsized().unwrap();
+    assert_eq!(slice, &concrete);
+    let (_, error) = TryCoerceUnsized::<&[u32]>::try_coerce_unsized(&bytes[1..5]).unwrap_err();
+    assert_eq!(error, crate::pod::PodCastError::Misaligned);
+
+    // a length bound checked by the coercion instead of asserted
+    #[derive(Debug)]
+    struct FixedStringWithLen<const N: usize>(usize, [u8; N]);
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Len,
+        Utf8,
+    }
+
+    // SAFETY: The length is checked against the capacity and the bytes are checked to be valid
+    // UTF-8, the data address points to the byte array
+    unsafe impl<const N: usize> crate::unsize::TryUnsize<str> for FixedStringWithLen<N> {
+        type Error = Error;
+
+        unsafe fn try_target_metadata(self: *const Self) -> Result<usize, Error> {
+            // SAFETY: self points to a live Self
+            let this = unsafe { &*self };
+            let bytes = this.1.get(..this.0).ok_or(Error::Len)?;
+            core::str::from_utf8(bytes).map_err(|_| Error::Utf8)?;
+            Ok(this.0)
+        }
+
+        unsafe fn target_data_address(self: *const Self) -> *const () {
+            // SAFETY: self points to a live Self
+            unsafe { addr_of!((*self).1).cast() }
+        }
+    }
+    let concrete = FixedStringWithLen(3, *b"foo\0\0");
+    let s: &str = (&concrete).try_coerce_unsized().unwrap();
+    assert_eq!(s, "foo");
+    let concrete = FixedStringWithLen(6, *b"foo\0\0");
+    let (_, error) = TryCoerceUnsized::<&str>::try_coerce_unsized(&concrete).unwrap_err();
+    assert_eq!(error, Error::Len);
+}
+
+#[test]
+fn resize() {
+    use crate::coerce_unsized::Resize;
+
+    let slice: alloc::boxed::Box<[i32]> = alloc::boxed::Box::new([0, 1, 2]).coerce_unsized();
+    let slice = Resize::<alloc::boxed::Box<[i32; 2]>>::try_resize(slice).unwrap_err();
+    let array: alloc::boxed::Box<[i32; 3]> = slice.try_resize().unwrap();
+    assert_eq!(*array, [0, 1, 2]);
+
+    let mut concrete = [0, 1, 2, 3];
+    let slice: &mut [i32] = &mut concrete;
+    let arrays: &mut [[i32; 2]] = slice.try_resize().unwrap();
+    arrays[1] = [4, 5];
+    assert_eq!(concrete, [0, 1, 4, 5]);
+    let slice: &[i32] = &concrete[..3];
+    assert!(Resize::<&[[i32; 2]]>::try_resize(slice).is_err());
+    let arrays: &[[i32; 1]; 3] = slice.try_resize().unwrap();
+    assert_eq!(arrays, &[[0], [1], [4]]);
+
+    let cells: alloc::rc::Rc<[core::cell::Cell<u8>]> =
+        alloc::rc::Rc::new(core::cell::Cell::new([1, 2])).coerce_unsized();
+    let cell: alloc::rc::Rc<core::cell::Cell<[u8; 2]>> = cells.try_resize().unwrap();
+    assert_eq!(cell.get(), [1, 2]);
+
+    let any: alloc::boxed::Box<dyn core::any::Any> = alloc::boxed::Box::new(1u8).coerce_unsized();
+    let any = Resize::<alloc::boxed::Box<u16>>::try_resize(any).unwrap_err();
+    let concrete: alloc::boxed::Box<u8> = any.try_resize().unwrap();
+    assert_eq!(*concrete, 1);
+    let any: alloc::sync::Arc<dyn core::any::Any + Send + Sync> =
+        alloc::sync::Arc::new(alloc::string::String::from("foo")).coerce_unsized();
+    let concrete: alloc::sync::Arc<alloc::string::String> = any.try_resize().unwrap();
+    assert_eq!(*concrete, "foo");
+    let mut concrete = 1u32;
+    let any: &mut dyn core::any::Any = (&mut concrete).coerce_unsized();
+    *Resize::<&mut u32>::try_resize(any).unwrap() = 2;
+    assert_eq!(concrete, 2);
+
+    #[derive(Debug)]
+    #[repr(transparent)]
+    struct FixedString<const N: usize>([u8; N]);
+
+    // SAFETY: The metadata returned by `target_metadata` is valid for a `str` object representing
+    // the `Self` object
+    unsafe impl<const N: usize> FromMetadataUnsize<str> for FixedString<N> {
+        fn target_metadata(
+            (): <Self as core::ptr::Pointee>::Metadata,
+        ) -> <str as core::ptr::Pointee>::Metadata {
+            N
+        }
+    }
+
+    // SAFETY: A `str` of `N` bytes is a valid `FixedString<N>`, and any bytes written to the
+    // `FixedString` have to be valid UTF-8 for the forward impl to be sound
+    unsafe impl<const N: usize> crate::unsize::TrySize<FixedString<N>> for str {
+        unsafe fn try_target_metadata(self: *const Self) -> Option<()> {
+            (core::ptr::metadata(self) == N).then_some(())
+        }
+    }
+    // SAFETY: A `str` of `N` bytes is laid out like `FixedString<N>`
+    unsafe impl<const N: usize> crate::unsize::TrySizeOwned<FixedString<N>> for str {}
+
+    let s: alloc::sync::Arc<str> = alloc::sync::Arc::from("foo");
+    let s = Resize::<alloc::sync::Arc<FixedString<4>>>::try_resize(s).unwrap_err();
+    let fixed: alloc::sync::Arc<FixedString<3>> = s.try_resize().unwrap();
+    assert_eq!(&fixed.0, b"foo");
+    let s: alloc::sync::Arc<str> = fixed.coerce_unsized();
+    assert_eq!(&*s, "foo");
+
+    let tail: alloc::boxed::Box<(u8, [i32])> =
+        alloc::boxed::Box::new((1u8, [2, 3])).coerce_unsized();
+    let tail = Resize::<alloc::boxed::Box<(u8, [i32; 3])>>::try_resize(tail).unwrap_err();
+    // unsized tuples don't implement `Debug`, so `unwrap` can't be used
+    let tuple: alloc::boxed::Box<(u8, [i32; 2])> = tail.try_resize().ok().unwrap();
+    assert_eq!(*tuple, (1, [2, 3]));
+
+    let cell: alloc::rc::Rc<core::cell::RefCell<[i32]>> =
+        alloc::rc::Rc::new(core::cell::RefCell::new([0, 1])).coerce_unsized();
+    // the metadata alone decides, so a mutably borrowed `RefCell` can be resized as well
+    let borrow = cell.borrow_mut();
+    let sized: alloc::rc::Rc<core::cell::RefCell<[i32; 2]>> = cell.clone().try_resize().unwrap();
+    assert!(sized.try_borrow().is_err());
+    drop(borrow);
+    assert_eq!(*sized.borrow(), [0, 1]);
+
+    let error: alloc::boxed::Box<dyn core::error::Error + Send + Sync> =
+        alloc::boxed::Box::new(crate::pod::PodCastError::Misaligned).coerce_unsized();
+    let error = Resize::<alloc::boxed::Box<core::fmt::Error>>::try_resize(error).unwrap_err();
+    let error: alloc::boxed::Box<crate::pod::PodCastError> = error.try_resize().unwrap();
+    assert_eq!(*error, crate::pod::PodCastError::Misaligned);
+
+    let mut concrete = [0u32; 2];
+    let bytes: &mut [u8] = (&mut concrete).coerce_unsized();
+    let words: &mut [u32] = bytes.try_resize().unwrap();
+    words[1] = 7;
+    assert_eq!(concrete, [0, 7]);
+    let bytes: &[u8] = (&concrete).coerce_unsized();
+    assert!(Resize::<&[u32]>::try_resize(&bytes[1..5]).is_err());
+
+    let mut concrete = [0, 1, 2];
+    let raw: *mut [i32] = &mut concrete;
+    let raw = Resize::<*mut [i32; 2]>::try_resize(raw).unwrap_err();
+    let raw: *mut [i32; 3] = raw.try_resize().unwrap();
+    // SAFETY: The pointer is derived from a unique reference to a live array of the same length
+    unsafe { (*raw)[2] = 3 };
+    assert_eq!(concrete, [0, 1, 3]);
+    let non_null: core::ptr::NonNull<[i32]> = core::ptr::NonNull::from(&concrete[..]);
+    let array: core::ptr::NonNull<[i32; 3]> = non_null.try_resize().unwrap();
+    // SAFETY: The pointer is derived from a reference to a live array of the same length
+    assert_eq!(unsafe { *array.as_ptr() }, [0, 1, 3]);
+
+    // weak pointers are resized without inspecting their possibly dropped pointee
+    let rc: alloc::rc::Rc<[i32]> = alloc::rc::Rc::new([0, 1]).coerce_unsized();
+    let weak = alloc::rc::Rc::downgrade(&rc);
+    let weak = Resize::<alloc::rc::Weak<[i32; 3]>>::try_resize(weak).unwrap_err();
+    let weak: alloc::rc::Weak<[i32; 2]> = weak.try_resize().unwrap();
+    assert_eq!(*weak.upgrade().unwrap(), [0, 1]);
+    drop(rc);
+    assert!(weak.upgrade().is_none());
+    let dangling: alloc::sync::Weak<[i32]> = alloc::sync::Weak::<[i32; 2]>::new().coerce_unsized();
+    let dangling: alloc::sync::Weak<[i32; 2]> = dangling.try_resize().unwrap();
+    assert!(dangling.upgrade().is_none());
+
+    let pinned: core::pin::Pin<alloc::boxed::Box<[i32]>> =
+        alloc::boxed::Box::pin([0, 1, 2]).coerce_unsized();
+    let pinned =
+        Resize::<core::pin::Pin<alloc::boxed::Box<[i32; 2]>>>::try_resize(pinned).unwrap_err();
+    let pinned: core::pin::Pin<alloc::boxed::Box<[i32; 3]>> = pinned.try_resize().unwrap();
+    assert_eq!(*pinned, [0, 1, 2]);
+}
+
+#[test]
+fn owning_coerce() {
+    let a = CountingAllocator::default();
+
+    let mut concrete = alloc::vec::Vec::with_capacity_in(3, &a);
+    concrete.extend([0, 1, 2]);
+    let ptr = concrete.as_ptr();
+    let coerced: alloc::boxed::Box<[i32], _> = concrete.coerce_unsized();
+    // the buffer is reused as is
+    assert_eq!(a.counts(), (1, 0));
+    assert_eq!((coerced.as_ptr(), &*coerced), (ptr, &[0, 1, 2][..]));
+    drop(coerced);
+    assert_eq!(a.counts(), (1, 1));
+
+    let mut concrete = alloc::vec::Vec::with_capacity_in(10, &a);
+    concrete.extend([0, 1, 2]);
+    let coerced: alloc::boxed::Box<[i32], _> = concrete.coerce_unsized();
+    // the spare capacity has to be shrunk away
+    assert_eq!(a.counts(), (3, 2));
+    assert_eq!(&*coerced, &[0, 1, 2]);
+    drop(coerced);
+    assert_eq!(a.counts(), (3, 3));
+
+    let mut concrete = alloc::vec::Vec::with_capacity_in(3, &a);
+    concrete.extend([0, 1, 2]);
+    let coerced: alloc::rc::Rc<[i32], _> = concrete.coerce_unsized();
+    // the reference counts need a new allocation, the vector's buffer is freed
+    assert_eq!(a.counts(), (5, 4));
+    assert_eq!(&*coerced, &[0, 1, 2]);
+    drop(coerced);
+    assert_eq!(a.counts(), (5, 5));
+
+    let mut concrete = alloc::vec::Vec::with_capacity_in(10, &a);
+    concrete.extend([0, 1, 2]);
+    let coerced: alloc::sync::Arc<[i32], _> = concrete.coerce_unsized();
+    assert_eq!(a.counts(), (7, 6));
+    assert_eq!(&*coerced, &[0, 1, 2]);
+    drop(coerced);
+    assert_eq!(a.counts(), (7, 7));
+
+    let concrete = alloc::string::String::from("foo");
+    let ptr = concrete.as_ptr();
+    let coerced: alloc::boxed::Box<str> = concrete.coerce_unsized();
+    assert_eq!((coerced.as_ptr(), &*coerced), (ptr, "foo"));
+    let coerced: alloc::rc::Rc<str> = alloc::string::String::from("foo").coerce_unsized();
+    assert_eq!(&*coerced, "foo");
+    let coerced: alloc::sync::Arc<str> = alloc::string::String::from("foo").coerce_unsized();
+    assert_eq!(&*coerced, "foo");
+}
+
 #[test]
 #[cfg(not(miri))]
 fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
 }
@@ -1,5 +1,15 @@
 //! This module experiments with a new Unsize definition, splitting it into two [`Unsize`] and [`FromMetadataUnsize`].
+use core::alloc::Allocator;
+use core::cell::{Cell, RefCell, UnsafeCell};
+use core::convert::Infallible;
+use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ptr::Pointee;
+use core::str::Utf8Error;
+
+#[cfg(feature = "std")]
+use std::sync::{Mutex, RwLock};
+
+pub use unsizing_experiments_derive::Unsize;
 
 // Note there was `ConstUnsize` trait before that had an associated constant for the metadata instead
 // but that trait is technically unnecessary, it is effectively `FromMetadataUnsize<Target>` where
@@ -52,6 +62,26 @@ where
     unsafe fn target_data_address(self: *const Self) -> *const ();
 }
 
+/// Types that can be "unsized" to a dynamically-sized type which may be written to.
+///
+/// This is required for unsizing behind mutable pointers, as the data address returned by
+/// [`Unsize::target_data_address`] may be derived from a shared reference, like the one returned by
+/// `Vec::as_ptr`, and writing through it would be undefined behaviour.
+///
+/// # Safety
+///
+/// - The implementation of [`UnsizeMut::target_data_address_mut`] must return the same address as
+///   [`Unsize::target_data_address`], with the provenance of `self` permitting writes to the target.
+pub unsafe trait UnsizeMut<Target>: Unsize<Target>
+where
+    Target: ?Sized,
+{
+    /// # Safety
+    ///
+    /// `self` must point to a valid instance of `Self`.
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut ();
+}
+
 /// # Safety
 ///
 /// - The implementation of [`FromMetadataUnsize::target_metadata`] must return metadata that is valid for
@@ -84,6 +114,171 @@ where
     }
 }
 
+// SAFETY: The data address is `self`, just like for `Unsize::target_data_address`
+unsafe impl<T, Target> UnsizeMut<Target> for T
+where
+    Target: ?Sized,
+    T: FromMetadataUnsize<Target> + ?Sized,
+{
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+        self.cast()
+    }
+}
+
+/// Types that can be "unsized" to a dynamically-sized type after checking at runtime that the
+/// object is valid for the target, like bytes being valid UTF-8 for `str`.
+///
+/// Every [`Unsize`] type implements this trait, with [`Infallible`] as its error.
+///
+/// # Safety
+///
+/// - If [`TryUnsize::try_target_metadata`] returns metadata, it must be valid for the object
+///   pointed to by the output of [`TryUnsize::target_data_address`], and that object must be a
+///   valid `Target`.
+pub unsafe trait TryUnsize<Target>
+where
+    Target: ?Sized,
+{
+    /// The error returned if the object is not valid for the target.
+    type Error;
+
+    /// # Safety
+    ///
+    /// `self` must point to a valid instance of `Self`.
+    unsafe fn try_target_metadata(
+        self: *const Self,
+    ) -> Result<<Target as Pointee>::Metadata, Self::Error>;
+    /// # Safety
+    ///
+    /// `self` must point to a valid instance of `Self`.
+    unsafe fn target_data_address(self: *const Self) -> *const ();
+}
+
+/// Types that can be fallibly "unsized" to a dynamically-sized type which may be written to, see
+/// [`UnsizeMut`].
+///
+/// # Safety
+///
+/// - The implementation of [`TryUnsizeMut::target_data_address_mut`] must return the same address
+///   as [`TryUnsize::target_data_address`], with the provenance of `self` permitting writes to the
+///   target. Any valid `Target` written has to leave a valid `Self` behind.
+pub unsafe trait TryUnsizeMut<Target>: TryUnsize<Target>
+where
+    Target: ?Sized,
+{
+    /// # Safety
+    ///
+    /// `self` must point to a valid instance of `Self`.
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut ();
+}
+
+/// Types that can be fallibly "unsized" in place, allowing owning pointers like `Box` to be
+/// coerced as well. This is the fallible counterpart of [`FromMetadataUnsize`], which implies it.
+///
+/// # Safety
+///
+/// - [`TryUnsize::target_data_address`] and [`TryUnsizeMut::target_data_address_mut`] must return
+///   `self`.
+/// - If [`TryUnsize::try_target_metadata`] returns metadata, `Target` with that metadata must be
+///   layout compatible with the object pointed to by `self`.
+pub unsafe trait TryUnsizeOwned<Target>: TryUnsizeMut<Target>
+where
+    Target: ?Sized,
+{
+}
+
+// SAFETY: The metadata and data address are the ones of the `Unsize` impl
+unsafe impl<T, Target> TryUnsize<Target> for T
+where
+    Target: ?Sized,
+    T: Unsize<Target> + ?Sized,
+{
+    type Error = Infallible;
+
+    unsafe fn try_target_metadata(
+        self: *const Self,
+    ) -> Result<<Target as Pointee>::Metadata, Infallible> {
+        // SAFETY: self points to a valid instance as per calling contract
+        Ok(unsafe { Unsize::target_metadata(self) })
+    }
+
+    unsafe fn target_data_address(self: *const Self) -> *const () {
+        // SAFETY: self points to a valid instance as per calling contract
+        unsafe { Unsize::target_data_address(self) }
+    }
+}
+
+// SAFETY: The data address is the one of the `UnsizeMut` impl
+unsafe impl<T, Target> TryUnsizeMut<Target> for T
+where
+    Target: ?Sized,
+    T: UnsizeMut<Target> + ?Sized,
+{
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+        // SAFETY: self points to a valid instance as per calling contract
+        unsafe { UnsizeMut::target_data_address_mut(self) }
+    }
+}
+
+// SAFETY: `FromMetadataUnsize` unsizes in place and requires layout compatibility
+unsafe impl<T, Target> TryUnsizeOwned<Target> for T
+where
+    Target: ?Sized,
+    T: FromMetadataUnsize<Target> + ?Sized,
+{
+}
+
+/// Types that can be "sized" back to a type that unsizes to them, after checking at runtime that
+/// the object actually is a valid `Target`. This is the in-place inverse of the unsizing impls,
+/// like `[T] -> [T; N]` checking the length or `dyn Any -> T` checking the type id.
+///
+/// # Safety
+///
+/// - If [`TrySize::try_target_metadata`] returns metadata, the object pointed to by `self` must be
+///   a valid `Target` with that metadata.
+/// - Any valid `Target` written to the object has to leave a valid `Self` behind.
+pub unsafe trait TrySize<Target>
+where
+    Target: ?Sized,
+{
+    /// # Safety
+    ///
+    /// `self` must point to a valid instance of `Self`.
+    unsafe fn try_target_metadata(self: *const Self) -> Option<<Target as Pointee>::Metadata>;
+}
+
+/// Types that can be "sized" back in place, allowing owning pointers like `Box` to be resized as
+/// well. This is the counterpart of [`TryUnsizeOwned`].
+///
+/// # Safety
+///
+/// If [`TrySize::try_target_metadata`] returns metadata, `Target` with that metadata must be layout
+/// compatible with the object pointed to by `self`.
+pub unsafe trait TrySizeOwned<Target>: TrySize<Target>
+where
+    Target: ?Sized,
+{
+}
+
+/// Types that can be "sized" back based on their metadata alone, the counterpart of
+/// [`FromMetadataUnsize`].
+///
+/// Tuples and wrappers like `Cell<T>` forward their [`TrySize`] impls to this trait, as the object
+/// behind them can't always be inspected, a `RefCell` may be mutably borrowed for example.
+///
+/// # Safety
+///
+/// [`FromMetadataTrySize::try_target_metadata_from`] must return what
+/// [`TrySize::try_target_metadata`] returns for any object with the given metadata.
+pub unsafe trait FromMetadataTrySize<Target>: TrySizeOwned<Target>
+where
+    Target: ?Sized,
+{
+    fn try_target_metadata_from(
+        metadata: <Self as Pointee>::Metadata,
+    ) -> Option<<Target as Pointee>::Metadata>;
+}
+
 // SAFETY: `Unsize::target_metadata` returns the same value as `FromMetadataUnsize::TARGET_METADATA`
 unsafe impl<T, const N: usize> FromMetadataUnsize<[T]> for [T; N] {
     fn target_metadata((): <Self as Pointee>::Metadata) -> <[T] as Pointee>::Metadata {
@@ -91,8 +286,31 @@ unsafe impl<T, const N: usize> FromMetadataUnsize<[T]> for [T; N] {
     }
 }
 
+// SAFETY: `[T; N]` has the same layout as `N` consecutive `T`s, so `len` arrays span exactly
+// `len * N` elements
+unsafe impl<T, const N: usize> FromMetadataUnsize<[T]> for [[T; N]] {
+    fn target_metadata(len: <Self as Pointee>::Metadata) -> <[T] as Pointee>::Metadata {
+        // the byte size of an allocated slice fits into an `isize`, so this can only overflow for
+        // zero-sized `T`, where we can't produce a length that covers all elements
+        len.checked_mul(N)
+            .expect("the flattened slice length overflows `usize`")
+    }
+}
+
+// SAFETY: Same as for `[[T; N]]`, with the length known statically
+unsafe impl<T, const N: usize, const M: usize> FromMetadataUnsize<[T]> for [[T; N]; M] {
+    fn target_metadata((): <Self as Pointee>::Metadata) -> <[T] as Pointee>::Metadata {
+        N.checked_mul(M)
+            .expect("the flattened slice length overflows `usize`")
+    }
+}
+
+// There is deliberately no `[[u8; N]] -> str` counterpart, the bytes of an arbitrary byte array are
+// not necessarily valid UTF-8. Flattening only preserves the validity of the elements themselves,
+// so a `str` view requires a source that already guarantees UTF-8 for its whole contents.
+
 // SAFETY: The metadata returned by `target_metadata` belongs to the object pointed to by the pointer returned by `target_address`
-unsafe impl<T> Unsize<[T]> for alloc::vec::Vec<T> {
+unsafe impl<T, A: Allocator> Unsize<[T]> for alloc::vec::Vec<T, A> {
     unsafe fn target_metadata(self: *const Self) -> <[T] as Pointee>::Metadata {
         // SAFETY: self is a valid pointer
         unsafe { (*self).len() }
@@ -103,6 +321,15 @@ unsafe impl<T> Unsize<[T]> for alloc::vec::Vec<T> {
     }
 }
 
+// SAFETY: `as_mut_ptr` returns the same address as `as_ptr`, but permits writes
+unsafe impl<T, A: Allocator> UnsizeMut<[T]> for alloc::vec::Vec<T, A> {
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+        // SAFETY: self is a valid pointer
+        unsafe { (*self).as_mut_ptr().cast() }
+    }
+}
+
+// Note that `String` is not generic over an allocator (yet), so there is nothing to thread through here.
 // SAFETY: The metadata returned by `target_metadata` belongs to the object pointed to by the pointer returned by `target_address`
 unsafe impl Unsize<str> for alloc::string::String {
     unsafe fn target_metadata(self: *const Self) -> <str as Pointee>::Metadata {
@@ -115,6 +342,63 @@ unsafe impl Unsize<str> for alloc::string::String {
     }
 }
 
+// SAFETY: `as_mut_ptr` returns the same address as `as_ptr`, but permits writes
+unsafe impl UnsizeMut<str> for alloc::string::String {
+    unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+        // SAFETY: self is a valid pointer
+        unsafe { (*self).as_mut_ptr().cast() }
+    }
+}
+
+// Note that this is deliberately neither `FromMetadataUnsize` nor `UnsizeMut`, as that would allow
+// coercing `&mut str` to `&mut [u8]` and writing invalid UTF-8 through it. The owning pointers give
+// up their `str` when being coerced, see their `CoerceUnsized` impls.
+// SAFETY: `str` has the same layout as `[u8]` and its length metadata is the number of bytes
+unsafe impl Unsize<[u8]> for str {
+    unsafe fn target_metadata(self: *const Self) -> <[u8] as Pointee>::Metadata {
+        core::ptr::metadata(self)
+    }
+
+    unsafe fn target_data_address(self: *const Self) -> *const () {
+        self.cast()
+    }
+}
+
+macro_rules! impl_try_unsize_utf8 {
+    ($(<$(const $n:ident: usize)?> $ty:ty),*) => {
+        $(
+            // SAFETY: The bytes are checked to be valid UTF-8, `str` has the same layout as `[u8]`
+            unsafe impl<$(const $n: usize)?> TryUnsize<str> for $ty {
+                type Error = Utf8Error;
+
+                unsafe fn try_target_metadata(
+                    self: *const Self,
+                ) -> Result<<str as Pointee>::Metadata, Utf8Error> {
+                    // SAFETY: self points to a valid instance as per calling contract
+                    let bytes: &[u8] = unsafe { &*self };
+                    core::str::from_utf8(bytes).map(str::len)
+                }
+
+                unsafe fn target_data_address(self: *const Self) -> *const () {
+                    self.cast()
+                }
+            }
+
+            // SAFETY: The data address is `self`, writes through a `str` keep the bytes valid UTF-8
+            unsafe impl<$(const $n: usize)?> TryUnsizeMut<str> for $ty {
+                unsafe fn target_data_address_mut(self: *mut Self) -> *mut () {
+                    self.cast()
+                }
+            }
+
+            // SAFETY: The data address is `self`, and the `str` spans exactly the bytes
+            unsafe impl<$(const $n: usize)?> TryUnsizeOwned<str> for $ty {}
+        )*
+    };
+}
+
+impl_try_unsize_utf8!(<> [u8], <const N: usize> [u8; N]);
+
 /* the compiler will generate impls of the form:
 unsafe impl<trait Trait, T: Trait> FromMetadataUnsize<dyn Trait> for T {
     fn target_metadata(metadata: <Self as Pointee>::Metadata) -> <dyn Trait as Pointee>::Metadata {
@@ -125,6 +409,74 @@ Note that we require T to be sized here! otherwise we would lose the metadata of
 `str` could be coerced into trait objects which is not a thing today
 */
 
+/// Emulates the compiler's `FromMetadataUnsize<dyn Trait>` impl for types implementing `Trait`.
+///
+/// `impl_dyn_unsize!(dyn Trait + Send)` emits a blanket impl for all sized `T: Trait + Send`,
+/// trait parameters are declared up front as in `impl_dyn_unsize!(<X: Bound> dyn Trait<X>)`. The impl is
+/// generic over the lifetime of the trait object, so no lifetime bound may be given.
+///
+/// Due to the orphan rules the blanket impl can only be emitted within this crate, downstream
+/// crates have to list the implementing types instead, as in `impl_dyn_unsize!(i32, u8 => dyn Trait)`.
+#[macro_export]
+macro_rules! impl_dyn_unsize {
+    (@single $ty:ty => [$($bounds:tt)+]) => {
+        // SAFETY: The metadata is the vtable of the type for the trait object, taken from a pointer to it
+        unsafe impl<'__dyn> $crate::unsize::FromMetadataUnsize<dyn $($bounds)+ + '__dyn> for $ty
+        where
+            $ty: '__dyn,
+        {
+            fn target_metadata(
+                (): <Self as ::core::ptr::Pointee>::Metadata,
+            ) -> <dyn $($bounds)+ + '__dyn as ::core::ptr::Pointee>::Metadata {
+                ::core::ptr::metadata(::core::ptr::null::<$ty>() as *const (dyn $($bounds)+ + '__dyn))
+            }
+        }
+    };
+    (@types [$($ty:ty),+] => $bounds:tt) => {
+        $($crate::impl_dyn_unsize!(@single $ty => $bounds);)+
+    };
+    ($(<$($param:ident $(: $param_bound:path)?),* $(,)?>)? dyn $($bounds:tt)+) => {
+        // SAFETY: The metadata is the vtable of `__T` for the trait object, taken from a pointer to `__T`
+        unsafe impl<'__dyn, __T $($(, $param $(: $param_bound)?)*)?> $crate::unsize::FromMetadataUnsize<dyn $($bounds)+ + '__dyn>
+            for __T
+        where
+            __T: $($bounds)+ + '__dyn,
+        {
+            fn target_metadata(
+                (): <Self as ::core::ptr::Pointee>::Metadata,
+            ) -> <dyn $($bounds)+ + '__dyn as ::core::ptr::Pointee>::Metadata {
+                ::core::ptr::metadata(::core::ptr::null::<__T>() as *const (dyn $($bounds)+ + '__dyn))
+            }
+        }
+    };
+    ($($ty:ty),+ $(,)? => dyn $($bounds:tt)+) => {
+        $crate::impl_dyn_unsize!(@types [$($ty),+] => [$($bounds)+]);
+    };
+}
+
+/// Invokes [`impl_dyn_unsize!`] for the trait object itself and its `Send` and `Sync` variants.
+macro_rules! impl_dyn_unsize_auto {
+    ($(<$($param:ident $(: $param_bound:path)?),*>)? dyn $($bounds:tt)+) => {
+        impl_dyn_unsize!($(<$($param $(: $param_bound)?),*>)? dyn $($bounds)+);
+        impl_dyn_unsize!($(<$($param $(: $param_bound)?),*>)? dyn $($bounds)+ + Send);
+        impl_dyn_unsize!($(<$($param $(: $param_bound)?),*>)? dyn $($bounds)+ + Sync);
+        impl_dyn_unsize!($(<$($param $(: $param_bound)?),*>)? dyn $($bounds)+ + Send + Sync);
+    };
+}
+
+// the compiler impls for the object safe traits of core
+impl_dyn_unsize_auto!(dyn core::fmt::Debug);
+impl_dyn_unsize_auto!(dyn core::fmt::Display);
+impl_dyn_unsize_auto!(dyn core::any::Any);
+impl_dyn_unsize_auto!(dyn core::error::Error);
+// Note that these can't cover higher-ranked signatures like `dyn Fn(&str)`, as there is no way to
+// be generic over those
+impl_dyn_unsize_auto!(<Args: core::marker::Tuple, R> dyn Fn<Args, Output = R>);
+impl_dyn_unsize_auto!(<Args: core::marker::Tuple, R> dyn FnMut<Args, Output = R>);
+impl_dyn_unsize_auto!(<Args: core::marker::Tuple, R> dyn FnOnce<Args, Output = R>);
+impl_dyn_unsize_auto!(<I> dyn Iterator<Item = I>);
+impl_dyn_unsize_auto!(<O> dyn core::future::Future<Output = O>);
+
 /* trait upcasting: the compiler will generate impls of the form:
 unsafe impl<trait Trait, trait Super> FromMetadataUnsize<dyn Super> for dyn Trait where dyn Trait: Super {
     unsafe fn target_metadata(metadata: <Self as Pointee>::Metadata) -> <dyn super as Pointee>::Metadata {
@@ -133,6 +485,62 @@ unsafe impl<trait Trait, trait Super> FromMetadataUnsize<dyn Super> for dyn Trai
 }
 */
 
+/// Upcasts the metadata of `Sub` to the metadata of `Super`, by applying the builtin unsizing
+/// coercion to a raw pointer carrying that metadata.
+pub fn upcast_metadata<Sub, Super>(
+    metadata: <Sub as Pointee>::Metadata,
+) -> <Super as Pointee>::Metadata
+where
+    Sub: ?Sized + core::marker::Unsize<Super>,
+    Super: ?Sized,
+{
+    let sub: *const Sub = core::ptr::from_raw_parts(core::ptr::null::<()>(), metadata);
+    let sup: *const Super = sub;
+    core::ptr::metadata(sup)
+}
+
+/// Emulates the compiler's trait upcasting `FromMetadataUnsize<dyn Super>` impl for `dyn Sub`.
+///
+/// `impl_upcast!(dyn Sub => dyn Super => dyn Base)` declares an upcast for every pair of the
+/// chain, so `dyn Sub` can be upcast to both `dyn Super` and `dyn Base`. The metadata is obtained
+/// via [`upcast_metadata`], so the traits have to actually be supertraits of one another. As with
+/// [`impl_dyn_unsize!`], the impls are generic over the lifetime of the trait objects, so no
+/// lifetime bounds may be given.
+#[macro_export]
+macro_rules! impl_upcast {
+    (@edge [$($sub:tt)+] [$($super:tt)+]) => {
+        // SAFETY: The metadata is the builtin upcast of the vtable of `dyn Sub`
+        unsafe impl<'__dyn> $crate::unsize::FromMetadataUnsize<dyn $($super)+ + '__dyn>
+            for dyn $($sub)+ + '__dyn
+        {
+            fn target_metadata(
+                metadata: <Self as ::core::ptr::Pointee>::Metadata,
+            ) -> <dyn $($super)+ + '__dyn as ::core::ptr::Pointee>::Metadata {
+                $crate::unsize::upcast_metadata::<Self, dyn $($super)+ + '__dyn>(metadata)
+            }
+        }
+    };
+    // declare the edges from the first trait object to all later ones, then continue with the rest
+    (@edges $sub:tt $($super:tt)*) => {
+        $($crate::impl_upcast!(@edge $sub $super);)*
+        $crate::impl_upcast!(@edges $($super)*);
+    };
+    (@edges) => {};
+    // split the chain into the bounds of the individual trait objects
+    (@split [$($done:tt)*] [$($current:tt)*] => dyn $($rest:tt)*) => {
+        $crate::impl_upcast!(@split [$($done)* [$($current)*]] [] $($rest)*);
+    };
+    (@split [$($done:tt)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::impl_upcast!(@split [$($done)*] [$($current)* $next] $($rest)*);
+    };
+    (@split [$($done:tt)*] [$($current:tt)*]) => {
+        $crate::impl_upcast!(@edges $($done)* [$($current)*]);
+    };
+    (dyn $($chain:tt)+) => {
+        $crate::impl_upcast!(@split [] [] $($chain)+);
+    };
+}
+
 // Note that this impl is observable on stable rust already
 /* the compiler will generate impls of the form:
 unsafe impl<T, U> FromMetadataUnsize<Foo<U>> for Foo<T>
@@ -150,3 +558,303 @@ where
     }
 }
 */
+
+// The compiler treats tuples whose last element is unsized like structs, see the
+// `unsized_tuple_coercion` feature. This implements the above impl for them, taking the
+// unsized element along with the sized ones preceding it.
+macro_rules! impl_tuple_tail_unsize {
+    (@impl $($A:ident)*) => {
+        // SAFETY: The last element of a tuple is its tail, just like the last field of a struct
+        unsafe impl<$($A,)* T: ?Sized + FromMetadataUnsize<U>, U: ?Sized>
+            FromMetadataUnsize<($($A,)* U,)> for ($($A,)* T,)
+        {
+            fn target_metadata(
+                metadata: <Self as Pointee>::Metadata,
+            ) -> <($($A,)* U,) as Pointee>::Metadata {
+                <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+            }
+        }
+    };
+    () => {
+        impl_tuple_tail_unsize!(@impl);
+    };
+    ($A:ident $($As:ident)*) => {
+        impl_tuple_tail_unsize!(@impl $A $($As)*);
+        impl_tuple_tail_unsize!($($As)*);
+    };
+}
+
+impl_tuple_tail_unsize!(A1 A2 A3 A4 A5 A6 A7 A8 A9 A10 A11);
+
+/*
+ * The above impl for the unsizable wrapper types of core, alloc and std. These accept `T: ?Sized`,
+ * so their metadata is the metadata of `T`, whatever their private fields look like.
+ */
+
+// SAFETY: `Cell<T>` has the same in-memory representation as its inner type `T`
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> FromMetadataUnsize<Cell<U>> for Cell<T> {
+    fn target_metadata(metadata: <Self as Pointee>::Metadata) -> <Cell<U> as Pointee>::Metadata {
+        <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+    }
+}
+
+// SAFETY: `UnsafeCell<T>` has the same in-memory representation as its inner type `T`
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> FromMetadataUnsize<UnsafeCell<U>>
+    for UnsafeCell<T>
+{
+    fn target_metadata(
+        metadata: <Self as Pointee>::Metadata,
+    ) -> <UnsafeCell<U> as Pointee>::Metadata {
+        <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+    }
+}
+
+// SAFETY: `RefCell<T>` supports `T: ?Sized`, so the compiler unsizes `RefCell<[T; N]>` to
+// `RefCell<[T]>`. A struct can only be unsized through its one unsized field, which has to hold
+// the `T`, so the metadata of `RefCell<T>` is that of `T`. This impl emulates the compiler's
+// struct impl.
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> FromMetadataUnsize<RefCell<U>>
+    for RefCell<T>
+{
+    fn target_metadata(metadata: <Self as Pointee>::Metadata) -> <RefCell<U> as Pointee>::Metadata {
+        <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+    }
+}
+
+// SAFETY: `ManuallyDrop<T>` is guaranteed to have the same layout as `T`
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> FromMetadataUnsize<ManuallyDrop<U>>
+    for ManuallyDrop<T>
+{
+    fn target_metadata(
+        metadata: <Self as Pointee>::Metadata,
+    ) -> <ManuallyDrop<U> as Pointee>::Metadata {
+        <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+    }
+}
+
+// Note that `Wrapping<T>` requires `T: Sized`, so there is no unsized `Wrapping` to coerce to.
+
+// SAFETY: Same as for `RefCell`, `Mutex<T>` supports `T: ?Sized`, so its metadata is that of `T`
+#[cfg(feature = "std")]
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> FromMetadataUnsize<std::sync::Mutex<U>>
+    for std::sync::Mutex<T>
+{
+    fn target_metadata(
+        metadata: <Self as Pointee>::Metadata,
+    ) -> <std::sync::Mutex<U> as Pointee>::Metadata {
+        <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+    }
+}
+
+// SAFETY: Same as for `RefCell`, `RwLock<T>` supports `T: ?Sized`, so its metadata is that of `T`
+#[cfg(feature = "std")]
+unsafe impl<T: ?Sized + FromMetadataUnsize<U>, U: ?Sized> FromMetadataUnsize<std::sync::RwLock<U>>
+    for std::sync::RwLock<T>
+{
+    fn target_metadata(
+        metadata: <Self as Pointee>::Metadata,
+    ) -> <std::sync::RwLock<U> as Pointee>::Metadata {
+        <T as FromMetadataUnsize<U>>::target_metadata(metadata)
+    }
+}
+
+/*
+ * Transposing unsizes, turning a wrapper of an array into a slice of wrapped elements. These are
+ * not unsizes the compiler knows about, std provides one-off helpers like
+ * `Cell::as_slice_of_cells` for them instead.
+ */
+
+// SAFETY: `MaybeUninit<T>` is guaranteed to have the same layout as `T`, so `MaybeUninit<[T; N]>`
+// is laid out like `[T; N]`, which is laid out like `N` consecutive `MaybeUninit<T>`s. Neither
+// side drops or reads its elements, so no initialization state is being asserted.
+unsafe impl<T, const N: usize> FromMetadataUnsize<[MaybeUninit<T>]> for MaybeUninit<[T; N]> {
+    fn target_metadata((): <Self as Pointee>::Metadata) -> <[MaybeUninit<T>] as Pointee>::Metadata {
+        N
+    }
+}
+
+// SAFETY: `Cell<T>` has the same in-memory representation as `T`, so `Cell<[T; N]>` is laid out
+// like `N` consecutive `Cell<T>`s. Writing through one of the element cells is a write to the
+// array cell, which is fine as `Cell` is `!Sync` and never hands out references to its contents.
+unsafe impl<T, const N: usize> FromMetadataUnsize<[Cell<T>]> for Cell<[T; N]> {
+    fn target_metadata((): <Self as Pointee>::Metadata) -> <[Cell<T>] as Pointee>::Metadata {
+        N
+    }
+}
+
+// SAFETY: `UnsafeCell<T>` has the same in-memory representation as `T`, so `UnsafeCell<[T; N]>`
+// is laid out like `N` consecutive `UnsafeCell<T>`s. The interior mutability of the elements is
+// covered by the interior mutability of the array, accesses to them have to be synchronized
+// just like accesses to the whole array.
+unsafe impl<T, const N: usize> FromMetadataUnsize<[UnsafeCell<T>]> for UnsafeCell<[T; N]> {
+    fn target_metadata((): <Self as Pointee>::Metadata) -> <[UnsafeCell<T>] as Pointee>::Metadata {
+        N
+    }
+}
+
+/*
+ * Inverse unsizes, the way back for the in-place unsizes above. Note that these can't be derived
+ * from the `FromMetadataUnsize` impls in general. Comparing the metadata against the one of the
+ * forward impl works for lengths, but two vtables being equal (or not) says nothing about the
+ * types behind them, as the compiler may merge or duplicate vtables.
+ */
+
+// Implements `TrySize` and `TrySizeOwned` on top of a `FromMetadataTrySize` impl
+macro_rules! impl_try_size_from_metadata {
+    ([$($params:tt)*] $src:ty => $target:ty) => {
+        // SAFETY: The `FromMetadataTrySize` impl returns what this returns
+        unsafe impl<$($params)*> TrySize<$target> for $src {
+            unsafe fn try_target_metadata(
+                self: *const Self,
+            ) -> Option<<$target as Pointee>::Metadata> {
+                <Self as FromMetadataTrySize<$target>>::try_target_metadata_from(
+                    core::ptr::metadata(self),
+                )
+            }
+        }
+
+        // SAFETY: `FromMetadataTrySize` implies layout compatibility
+        unsafe impl<$($params)*> TrySizeOwned<$target> for $src {}
+    };
+}
+
+macro_rules! impl_try_size_len {
+    ($(<$elem:ident> $src:ty => $target:ty),* $(,)?) => {
+        $(
+            // SAFETY: A slice of `N` elements is laid out like an array of `N` elements, and the
+            // elements of both sides are the same, see the `FromMetadataUnsize` impl for the way there
+            unsafe impl<$elem, const N: usize> FromMetadataTrySize<$target> for $src {
+                fn try_target_metadata_from(len: usize) -> Option<()> {
+                    (len == N).then_some(())
+                }
+            }
+
+            impl_try_size_from_metadata!([$elem, const N: usize] $src => $target);
+        )*
+    };
+}
+
+impl_try_size_len!(
+    <T> [T] => [T; N],
+    <T> [MaybeUninit<T>] => MaybeUninit<[T; N]>,
+    <T> [Cell<T>] => Cell<[T; N]>,
+    <T> [UnsafeCell<T>] => UnsafeCell<[T; N]>,
+);
+
+// SAFETY: The slice is split into `len / N` arrays of `N` elements if that covers it exactly
+unsafe impl<T, const N: usize> FromMetadataTrySize<[[T; N]]> for [T] {
+    fn try_target_metadata_from(len: usize) -> Option<usize> {
+        match (len.checked_div(N), len.checked_rem(N)) {
+            (Some(arrays), Some(0)) => Some(arrays),
+            _ => None,
+        }
+    }
+}
+
+impl_try_size_from_metadata!([T, const N: usize] [T] => [[T; N]]);
+
+// SAFETY: The slice is laid out like `M` arrays of `N` elements if it has exactly `N * M` elements
+unsafe impl<T, const N: usize, const M: usize> FromMetadataTrySize<[[T; N]; M]> for [T] {
+    fn try_target_metadata_from(len: usize) -> Option<()> {
+        (N.checked_mul(M) == Some(len)).then_some(())
+    }
+}
+
+impl_try_size_from_metadata!([T, const N: usize, const M: usize] [T] => [[T; N]; M]);
+
+// The inverse of the tuple tail unsizes, forwarding the metadata of the tail
+macro_rules! impl_tuple_tail_try_size {
+    (@impl $($A:ident)*) => {
+        // SAFETY: The last element of a tuple is its tail, which is the only part of the tuple
+        // that changes
+        unsafe impl<$($A,)* T: ?Sized + FromMetadataTrySize<U>, U: ?Sized>
+            FromMetadataTrySize<($($A,)* U,)> for ($($A,)* T,)
+        {
+            fn try_target_metadata_from(
+                metadata: <Self as Pointee>::Metadata,
+            ) -> Option<<($($A,)* U,) as Pointee>::Metadata> {
+                <T as FromMetadataTrySize<U>>::try_target_metadata_from(metadata)
+            }
+        }
+
+        impl_try_size_from_metadata!(
+            [$($A,)* T: ?Sized + FromMetadataTrySize<U>, U: ?Sized] ($($A,)* T,) => ($($A,)* U,)
+        );
+    };
+    () => {
+        impl_tuple_tail_try_size!(@impl);
+    };
+    ($A:ident $($As:ident)*) => {
+        impl_tuple_tail_try_size!(@impl $A $($As)*);
+        impl_tuple_tail_try_size!($($As)*);
+    };
+}
+
+impl_tuple_tail_try_size!(A1 A2 A3 A4 A5 A6 A7 A8 A9 A10 A11);
+
+// The inverse of the wrapper tail unsizes, forwarding the metadata of the wrapped type. These
+// only go through `FromMetadataTrySize`, the wrapped object is not inspected.
+macro_rules! impl_wrapper_try_size {
+    ($($(#[$attr:meta])* $wrapper:ident),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            // SAFETY: The metadata of the wrapper is the metadata of the wrapped type, see the
+            // `FromMetadataUnsize` impl for the way there
+            unsafe impl<T: ?Sized + FromMetadataTrySize<U>, U: ?Sized>
+                FromMetadataTrySize<$wrapper<U>> for $wrapper<T>
+            {
+                fn try_target_metadata_from(
+                    metadata: <Self as Pointee>::Metadata,
+                ) -> Option<<$wrapper<U> as Pointee>::Metadata> {
+                    <T as FromMetadataTrySize<U>>::try_target_metadata_from(metadata)
+                }
+            }
+
+            $(#[$attr])*
+            impl_try_size_from_metadata!(
+                [T: ?Sized + FromMetadataTrySize<U>, U: ?Sized] $wrapper<T> => $wrapper<U>
+            );
+        )*
+    };
+}
+
+impl_wrapper_try_size!(
+    Cell,
+    UnsafeCell,
+    RefCell,
+    ManuallyDrop,
+    #[cfg(feature = "std")]
+    Mutex,
+    #[cfg(feature = "std")]
+    RwLock,
+);
+
+// The type id is the only way to tell what type is behind a vtable, so only trait objects that
+// expose it can be sized back. For other traits this would need a type id in every vtable.
+macro_rules! impl_try_size_dyn {
+    ($([$T:ident: $($bound:tt)+] $dyn:ty),* $(,)?) => {
+        $(
+            // SAFETY: The type id of the object is checked to be the one of `T`, so the object is a
+            // `T`. As it is the very same type, any `T` written leaves a valid object behind.
+            unsafe impl<$T: $($bound)+> TrySize<$T> for $dyn {
+                unsafe fn try_target_metadata(self: *const Self) -> Option<()> {
+                    // SAFETY: self points to a valid instance as per calling contract
+                    let this = unsafe { &*self };
+                    this.is::<$T>().then_some(())
+                }
+            }
+
+            // SAFETY: The object is a `T`, which is layout compatible with itself
+            unsafe impl<$T: $($bound)+> TrySizeOwned<$T> for $dyn {}
+        )*
+    };
+}
+
+impl_try_size_dyn!(
+    [T: core::any::Any] dyn core::any::Any,
+    [T: core::any::Any] dyn core::any::Any + Send,
+    [T: core::any::Any] dyn core::any::Any + Send + Sync,
+    [T: core::error::Error + 'static] dyn core::error::Error,
+    [T: core::error::Error + 'static] dyn core::error::Error + Send,
+    [T: core::error::Error + 'static] dyn core::error::Error + Send + Sync,
+);
@@ -0,0 +1,10 @@
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+fn main() {
+    let concrete = Some([0u8; 2]);
+    // a shared reference can't be turned into an option of a mutable one
+    let _: Option<&mut [u8]> = (&concrete).coerce_unsized();
+    let concrete: Result<[u8; 2], ()> = Ok([0; 2]);
+    // the error side is borrowed as well
+    let _: Result<&[u8], ()> = (&concrete).coerce_unsized();
+}
@@ -0,0 +1,21 @@
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+#[derive(CoerceUnsized)]
+struct TwoPointers<'a, T: ?Sized> {
+    first: &'a T,
+    second: Box<T>,
+}
+
+#[derive(CoerceUnsized)]
+enum TwoPointersInVariant<'a, T: ?Sized> {
+    None,
+    Both(&'a T, &'a T),
+}
+
+#[derive(CoerceUnsized)]
+struct Unmarked<T, U>(Box<T>, U);
+
+#[derive(CoerceUnsized)]
+struct OnlyPhantom<T>(std::marker::PhantomData<T>);
+
+fn main() {}
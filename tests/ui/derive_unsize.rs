@@ -0,0 +1,35 @@
+#![feature(ptr_metadata)]
+use unsizing_experiments::unsize::Unsize;
+
+#[derive(Unsize)]
+struct NotLast<T: ?Sized> {
+    first: Box<T>,
+    last: usize,
+}
+
+#[derive(Unsize)]
+struct MentionedTwice<T: ?Sized> {
+    marker: std::marker::PhantomData<Box<T>>,
+    last: T,
+}
+
+#[derive(Unsize)]
+enum Enum<T> {
+    Variant(T),
+}
+
+#[derive(Unsize)]
+#[unsize(target = [T], data = xs, len = len)]
+struct MissingUnsafe<T, const N: usize> {
+    len: usize,
+    xs: [T; N],
+}
+
+#[derive(Unsize)]
+#[unsize(unsafe(target = [T], data = xs, len = len))]
+struct NotAnArray<T> {
+    len: usize,
+    xs: Vec<T>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#![feature(ptr_metadata)]
+
+trait Foo {}
+trait Bar {}
+
+// `Bar` is not a supertrait of `Foo`
+unsizing_experiments::impl_upcast!(dyn Foo => dyn Bar);
+
+fn main() {}
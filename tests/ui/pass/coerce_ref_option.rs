@@ -0,0 +1,27 @@
+// Both the `&Option` impl and the plain reference impls apply to a `&Option` source, the expected
+// type picks between them
+use std::fmt::Debug;
+
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+fn first(bytes: Option<&[u8]>) -> Option<u8> {
+    bytes.and_then(|bytes| bytes.first().copied())
+}
+
+fn debug(value: &dyn Debug) -> String {
+    format!("{value:?}")
+}
+
+fn clear(bytes: Option<&mut [u8]>) {
+    bytes.unwrap().fill(0);
+}
+
+fn main() {
+    let concrete = Some([1u8, 2]);
+    assert_eq!(first((&concrete).coerce_unsized()), Some(1));
+    assert_eq!(debug((&concrete).coerce_unsized()), "Some([1, 2])");
+
+    let mut concrete = Some([1u8, 2]);
+    clear((&mut concrete).coerce_unsized());
+    assert_eq!(debug((&mut concrete).coerce_unsized()), "Some([0, 0])");
+}
@@ -0,0 +1,10 @@
+// Downstream crates must be able to use the derive without enabling any coherence features
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+#[derive(CoerceUnsized)]
+struct Wrapper<T: ?Sized>(Box<T>);
+
+fn main() {
+    let wrapper: Wrapper<[i32]> = Wrapper(Box::new([0, 1, 2])).coerce_unsized();
+    assert_eq!(&*wrapper.0, &[0, 1, 2][..]);
+}
@@ -0,0 +1,21 @@
+// `Box` is `#[fundamental]`, so a downstream crate can implement `CoerceUnsized` between boxes of
+// its own types, here one that moves the value into a new allocation. A pinned box must not
+// coerce through such an impl.
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+struct Local(PhantomPinned);
+struct Other(PhantomPinned);
+
+impl CoerceUnsized<Box<Other>> for Box<Local> {
+    fn coerce_unsized(self) -> Box<Other> {
+        Box::new(Other(self.0))
+    }
+}
+
+fn main() {
+    let pinned: Pin<Box<Local>> = Box::pin(Local(PhantomPinned));
+    let _: Pin<Box<Other>> = pinned.coerce_unsized();
+}
@@ -0,0 +1,11 @@
+// `Vec` unsizes by projecting to its buffer, which moves once the vector grows, so a pinned
+// reference to it must not coerce to a pinned slice
+use std::marker::PhantomPinned;
+use std::pin::{pin, Pin};
+
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+fn main() {
+    let vec: Pin<&mut Vec<PhantomPinned>> = pin!(vec![PhantomPinned]);
+    let _: Pin<&mut [PhantomPinned]> = vec.coerce_unsized();
+}
@@ -0,0 +1,31 @@
+// Regression test for https://github.com/rust-lang/rust/issues/68015
+use std::marker::PhantomPinned;
+use std::ops::Deref;
+use std::pin::Pin;
+
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+#[derive(CoerceUnsized)]
+struct Ptr<T: ?Sized>(Box<T>);
+
+impl Deref for Ptr<[u8; 1]> {
+    type Target = u8;
+
+    fn deref(&self) -> &u8 {
+        &self.0[0]
+    }
+}
+
+impl Deref for Ptr<[u8]> {
+    type Target = PhantomPinned;
+
+    fn deref(&self) -> &PhantomPinned {
+        Box::leak(Box::new(PhantomPinned))
+    }
+}
+
+fn main() {
+    let pinned = Pin::new(Ptr(Box::new([0])));
+    // the coercion switches to a `Deref` impl returning a `!Unpin` value that was never pinned
+    let _: Pin<Ptr<[u8]>> = CoerceUnsized::coerce_unsized(pinned);
+}
@@ -0,0 +1,9 @@
+use std::rc::Rc;
+
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+fn main() {
+    // the byte views would free the allocation with the alignment of `u8`
+    let _: Box<[u8]> = Box::new([0u32; 2]).coerce_unsized();
+    let _ = CoerceUnsized::<Rc<[u8]>>::coerce_unsized(Rc::<[u32]>::from([0; 2]));
+}
@@ -0,0 +1,7 @@
+use unsizing_experiments::coerce_unsized::CoerceUnsized;
+
+fn main() {
+    let mut s = String::from("foo");
+    // writing through the bytes could break the UTF-8 invariant of the `str`
+    let _: &mut [u8] = s.as_mut_str().coerce_unsized();
+}